@@ -0,0 +1,29 @@
+//! Presses keys on a [`WasmIosCalculator`], as the web calculator does.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wasm_ios_calculator::WasmIosCalculator;
+
+/// The ids of the web calculator's buttons, and one that is unknown.
+const IDS: &[&str] = &[
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", ".", "+", "-", "*", "/", "^", "√", "㏒",
+    "²√", "x²", "1/x", "=", "±", "%", "AC", "c", "i", "∠", "Rad", "EE", "π", "e", "Rand", "?",
+];
+
+fuzz_target!(|data: &[u8]| {
+    let Some((settings, keys)) = data.split_first() else {
+        return;
+    };
+
+    let mut calc = WasmIosCalculator::new();
+    calc.seed_random(0);
+    calc.set_complex_mode(settings & 1 == 1);
+    calc.set_display_width(settings >> 1);
+
+    for byte in keys {
+        calc.button_pressed(IDS[*byte as usize % IDS.len()]);
+        calc.output();
+        calc.aria_label();
+        calc.state();
+    }
+});
@@ -0,0 +1,23 @@
+//! Evaluates key sequences, as `ios-calc -e` does.
+#![no_main]
+
+use ios_calculator::{format_keys, Calculator, Key};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let mut calc = Calculator::new();
+    let Ok(keys) = calc.parse_keys(input) else {
+        return;
+    };
+    assert_eq!(
+        calc.parse_keys(&format_keys(&keys)).as_ref(),
+        Ok(&keys),
+        "Should parse formatted keys."
+    );
+
+    for key in keys.iter() {
+        calc.press(key);
+    }
+    calc.press(&Key::Equals);
+    calc.view();
+});
@@ -0,0 +1,44 @@
+//! Formats parsed values, and values entered a character at a time, as a
+//! [`Number`].
+#![no_main]
+
+use ios_calculator::{DisplayMode, Number};
+use libfuzzer_sys::fuzz_target;
+
+/// Formats a number with a range of display settings.
+fn format(number: &mut Number) {
+    for sf in [None, Some(0), Some(1), Some(9), Some(16), Some(u8::MAX)] {
+        number.set_sf(sf);
+        for display_mode in [
+            DisplayMode::Normal,
+            DisplayMode::Fix(2),
+            DisplayMode::Sci(u8::MAX),
+            DisplayMode::Eng(0),
+        ] {
+            number.set_display_mode(display_mode);
+            number.to_string();
+            number.spoken();
+        }
+    }
+    number.set_display_mode(DisplayMode::Normal);
+}
+
+fuzz_target!(|input: &str| {
+    if let Ok(value) = input.parse::<f64>() {
+        format(&mut Number::from(value));
+    }
+
+    // Enter the input as keys, setting the value for anything else.
+    let mut number = Number::from(0.);
+    for char in input.chars() {
+        match char {
+            '0'..='9' => number.append(char as u8 - b'0'),
+            '.' => number.decimalise(),
+            'e' => number.start_exponent(),
+            '-' => number.negate_exponent(),
+            'i' => number.imaginarise(),
+            _ => number.set_value(char as u32 as f64 * f64::MAX / 1e6),
+        }
+    }
+    format(&mut number);
+});
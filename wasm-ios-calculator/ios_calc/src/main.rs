@@ -0,0 +1,124 @@
+//! # ios-calc
+//!
+//! A command-line interface to the iOS calculator.
+//!
+//! Run without arguments for an interactive REPL, or evaluate keys
+//! non-interactively with `ios-calc -e "1+2="`.
+//!
+//! `ios-calc --rpc` serves JSON-RPC 2.0 over stdin and stdout, one message
+//! per line, for embedding the calculator in other processes.
+mod repl;
+mod rpc;
+
+use repl::Repl;
+use rpc::Rpc;
+use std::{
+    env,
+    io::{self, BufRead, IsTerminal, Write},
+    process::ExitCode,
+};
+
+/// The usage text printed for `--help`.
+const USAGE: &str = "\
+Usage:
+  ios-calc              Start an interactive REPL.
+  ios-calc -e <keys>    Evaluate keys and print the result, e.g. `ios-calc -e \"1+2\"`.
+  ios-calc --rpc        Serve JSON-RPC 2.0 over stdin and stdout, one message per line.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.as_slice() {
+        [] => interactive(),
+        [flag, keys] if flag == "-e" => evaluate(keys),
+        [flag] if flag == "--rpc" => serve(),
+        [flag] if flag == "-h" || flag == "--help" => {
+            println!("{}\n\n{}", USAGE, rpc::METHODS);
+            ExitCode::SUCCESS
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Evaluates a single line of keys, as entered into the REPL, and prints the
+/// display.
+fn evaluate(keys: &str) -> ExitCode {
+    match Repl::new().execute(keys) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the REPL until the input ends or `:quit` is entered.
+fn interactive() -> ExitCode {
+    let terminal = io::stdin().is_terminal();
+    let mut repl = Repl::new();
+
+    if terminal {
+        println!("{}\n\nType :help for help.", repl.display());
+    }
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        if terminal {
+            print!("> ");
+            io::stdout().flush().ok();
+        }
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(error)) => {
+                eprintln!("{}", error);
+                return ExitCode::FAILURE;
+            }
+            None => return ExitCode::SUCCESS,
+        };
+
+        match line.trim() {
+            ":quit" | ":q" => return ExitCode::SUCCESS,
+            line => match repl.execute(line) {
+                Ok(output) => println!("{}", output),
+                Err(error) => eprintln!("{}", error),
+            },
+        }
+    }
+}
+
+/// Serves JSON-RPC requests until the input ends.
+fn serve() -> ExitCode {
+    let mut rpc = Rpc::new();
+    let mut stdout = io::stdout().lock();
+
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                eprintln!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = rpc.handle(&line) {
+            // Flush so that the caller receives each response as it's written.
+            if writeln!(stdout, "{}", response)
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
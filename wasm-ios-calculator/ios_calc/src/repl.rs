@@ -0,0 +1,262 @@
+use ios_calculator::{AngleMode, Calculator, DisplayMode, Key};
+
+/// The help text listing the REPL's commands.
+pub const HELP: &str = "\
+Enter expressions, for example `1+2*3`, or keys, for example `12+` or `{AC}`.
+A line with an operator that ends in a number is evaluated as if `=` was
+pressed after it. End a line with an operator to continue the calculation on
+the next line.
+
+Commands:
+  :history             Show the lines entered and their results.
+  :undo                Undo the last line.
+  :mode                Show the current modes.
+  :mode <mode> [n]     Set a mode: normal, fix, sci, eng, deg, rad, real or complex.
+  :help                Show this help.
+  :quit                Exit.";
+
+/// A line entered into the REPL.
+struct Entry {
+    /// The line as it was entered.
+    input: String,
+    /// The display after the line was entered.
+    output: String,
+    /// The calculator before the line was entered.
+    previous: Calculator,
+}
+
+/// A read-eval-print loop around a [`Calculator`].
+#[derive(Default)]
+pub struct Repl {
+    calculator: Calculator,
+    history: Vec<Entry>,
+}
+
+impl Repl {
+    /// Create a new REPL.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Executes a line of keys or a command, returning the text to print.
+    ///
+    /// Equals is pressed after a line with an operator that ends in a number,
+    /// so that it is evaluated as an expression.
+    pub fn execute(&mut self, line: &str) -> Result<String, String> {
+        let line = line.trim();
+        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] => Ok(self.display()),
+            [":history"] => Ok(self.history()),
+            [":undo"] => self.undo(),
+            [":mode"] => Ok(self.mode()),
+            [":mode", mode @ ..] => {
+                let previous = self.calculator.clone();
+                self.set_mode(mode)?;
+                self.record(line, previous);
+                Ok(self.mode())
+            }
+            [":help"] => Ok(String::from(HELP)),
+            [command, ..] if command.starts_with(':') => {
+                Err(format!("Unknown command '{}'.", command))
+            }
+            _ => {
+                let keys = self
+                    .calculator
+                    .parse_keys(line)
+                    .map_err(|error| error.to_string())?;
+
+                let previous = self.calculator.clone();
+                for key in &keys {
+                    self.calculator.press(key);
+                }
+                if is_expression(&keys) {
+                    self.calculator.press(&Key::Equals);
+                }
+                self.record(line, previous);
+                Ok(self.display())
+            }
+        }
+    }
+
+    /// Returns the display exactly as the calculator formats it.
+    pub fn display(&self) -> String {
+        self.calculator.display().to_string()
+    }
+
+    /// Adds a line to the history.
+    fn record(&mut self, input: &str, previous: Calculator) {
+        self.history.push(Entry {
+            input: input.to_string(),
+            output: self.display(),
+            previous,
+        });
+    }
+
+    /// Returns the lines entered so far with their results.
+    fn history(&self) -> String {
+        if self.history.is_empty() {
+            return String::from("No history.");
+        }
+
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| format!("{:>3}  {}  →  {}", index + 1, entry.input, entry.output))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Restores the calculator to before the last line was entered.
+    fn undo(&mut self) -> Result<String, String> {
+        let entry = self
+            .history
+            .pop()
+            .ok_or_else(|| String::from("Nothing to undo."))?;
+        self.calculator = entry.previous;
+        Ok(self.display())
+    }
+
+    /// Describes the calculator's current modes.
+    fn mode(&self) -> String {
+        let display_mode = match self.calculator.display_mode() {
+            DisplayMode::Normal => String::from("normal"),
+            DisplayMode::Fix(decimals) => format!("fix {}", decimals),
+            DisplayMode::Sci(decimals) => format!("sci {}", decimals),
+            DisplayMode::Eng(decimals) => format!("eng {}", decimals),
+        };
+        let angle_mode = match self.calculator.angle_mode() {
+            AngleMode::Degrees => "deg",
+            AngleMode::Radians => "rad",
+        };
+        let complex = if self.calculator.complex_mode() {
+            "complex"
+        } else {
+            "real"
+        };
+        format!("{}, {}, {}", display_mode, angle_mode, complex)
+    }
+
+    /// Sets one of the calculator's modes from its name and arguments.
+    fn set_mode(&mut self, mode: &[&str]) -> Result<(), String> {
+        let decimals = |arguments: &[&str]| match arguments {
+            [decimals] => decimals
+                .parse::<u8>()
+                .map_err(|_| format!("Invalid number of decimal places '{}'.", decimals)),
+            _ => Err(String::from("Expected a number of decimal places.")),
+        };
+
+        match mode {
+            ["normal"] => self.calculator.set_display_mode(DisplayMode::Normal),
+            ["fix", arguments @ ..] => self
+                .calculator
+                .set_display_mode(DisplayMode::Fix(decimals(arguments)?)),
+            ["sci", arguments @ ..] => self
+                .calculator
+                .set_display_mode(DisplayMode::Sci(decimals(arguments)?)),
+            ["eng", arguments @ ..] => self
+                .calculator
+                .set_display_mode(DisplayMode::Eng(decimals(arguments)?)),
+            ["deg"] => self.calculator.set_angle_mode(AngleMode::Degrees),
+            ["rad"] => self.calculator.set_angle_mode(AngleMode::Radians),
+            ["real"] => self.calculator.set_complex_mode(false),
+            ["complex"] => self.calculator.set_complex_mode(true),
+            _ => return Err(format!("Unknown mode '{}'.", mode.join(" "))),
+        }
+        Ok(())
+    }
+}
+
+/// Checks if keys are an expression to evaluate: they have an operator and
+/// end by entering or changing a number, rather than with an operator or a
+/// key such as `=` or `{AC}`.
+fn is_expression(keys: &[Key]) -> bool {
+    let operator = keys.iter().any(|key| matches!(key, Key::Operator(..)));
+    operator
+        && !matches!(
+            keys.last(),
+            None | Some(Key::Operator(..) | Key::Equals | Key::Clear | Key::Polar | Key::AngleMode)
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Repl;
+
+    #[test]
+    fn evaluates_keys() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.execute("12+3="), Ok(String::from("15")));
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        let mut repl = Repl::new();
+        assert_eq!(
+            repl.execute("1+2*3"),
+            Ok(String::from("7")),
+            "Should press equals after an expression."
+        );
+        assert_eq!(repl.execute("{²√}"), Ok(String::from("2.64575131")));
+    }
+
+    #[test]
+    fn continues_keys() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.execute("12+"), Ok(String::from("12")));
+        assert_eq!(repl.execute("3*"), Ok(String::from("3")));
+        assert_eq!(repl.execute("2"), Ok(String::from("2")));
+        assert_eq!(repl.execute("="), Ok(String::from("18")));
+    }
+
+    #[test]
+    fn keeps_memory() {
+        let mut repl = Repl::new();
+        repl.execute("2*3=").unwrap();
+        assert_eq!(
+            repl.execute("="),
+            Ok(String::from("18")),
+            "Should repeat the last operation."
+        );
+    }
+
+    #[test]
+    fn undo() {
+        let mut repl = Repl::new();
+        repl.execute("1+2=").unwrap();
+        repl.execute("*4=").unwrap();
+        assert_eq!(repl.execute(":undo"), Ok(String::from("3")));
+        assert_eq!(repl.execute(":undo"), Ok(String::from("0")));
+        assert!(repl.execute(":undo").is_err());
+    }
+
+    #[test]
+    fn history() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.execute(":history"), Ok(String::from("No history.")));
+
+        repl.execute("1+2=").unwrap();
+        assert_eq!(
+            repl.execute(":history"),
+            Ok(String::from("  1  1+2=  →  3"))
+        );
+    }
+
+    #[test]
+    fn mode() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.execute(":mode"), Ok(String::from("normal, deg, real")));
+        assert_eq!(
+            repl.execute(":mode fix 2"),
+            Ok(String::from("fix 2, deg, real"))
+        );
+        assert_eq!(repl.execute("1="), Ok(String::from("1.00")));
+        assert!(repl.execute(":mode fix").is_err());
+    }
+
+    #[test]
+    fn unknown_input() {
+        let mut repl = Repl::new();
+        assert!(repl.execute(":nothing").is_err());
+        assert!(repl.execute("1+x").is_err());
+    }
+}
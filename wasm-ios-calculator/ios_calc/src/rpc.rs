@@ -0,0 +1,370 @@
+use ios_calculator::{AngleMode, Calculator, Error, Key};
+use serde_json::{json, Map, Value};
+
+/// The JSON-RPC error codes.
+pub mod code {
+    /// The request isn't valid JSON.
+    pub const PARSE_ERROR: i64 = -32700;
+    /// The request isn't a valid JSON-RPC request.
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// The method doesn't exist.
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// The parameters are missing or have the wrong type.
+    pub const INVALID_PARAMS: i64 = -32602;
+
+    /// [`Error::EmptyBuffer`](ios_calculator::Error::EmptyBuffer).
+    pub const EMPTY_BUFFER: i64 = 1;
+    /// [`Error::MissingOperand`](ios_calculator::Error::MissingOperand).
+    pub const MISSING_OPERAND: i64 = 2;
+    /// [`Error::UnexpectedToken`](ios_calculator::Error::UnexpectedToken).
+    pub const UNEXPECTED_TOKEN: i64 = 3;
+    /// [`Error::UnknownKey`](ios_calculator::Error::UnknownKey).
+    pub const UNKNOWN_KEY: i64 = 4;
+    /// [`Error::NotRecording`](ios_calculator::Error::NotRecording).
+    pub const NOT_RECORDING: i64 = 5;
+    /// [`Error::UnknownMacro`](ios_calculator::Error::UnknownMacro).
+    pub const UNKNOWN_MACRO: i64 = 6;
+    /// [`Error::ReservedOperator`](ios_calculator::Error::ReservedOperator).
+    pub const RESERVED_OPERATOR: i64 = 7;
+}
+
+/// The methods listed in the usage text.
+pub const METHODS: &str = "\
+Methods:
+  buttonPressed(id)       Press the key with an id, such as \"7\", \"+\" or \"AC\".
+  output()                The text shown on the display.
+  activeOperator()        The id of the active operator, or null.
+  showAllClear()          True if the clear key reads 'AC'.
+  evaluate(expression)    Evaluate keys on a new calculator, e.g. \"1+2*3\".
+  state()                 Everything there is to display.
+  reset()                 Replace the calculator with a new one.";
+
+/// An error returned to the caller.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: &str) -> Self {
+        RpcError {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<Error> for RpcError {
+    fn from(error: Error) -> Self {
+        let code = match error {
+            Error::EmptyBuffer => code::EMPTY_BUFFER,
+            Error::MissingOperand => code::MISSING_OPERAND,
+            Error::UnexpectedToken(..) => code::UNEXPECTED_TOKEN,
+            Error::UnknownKey(..) => code::UNKNOWN_KEY,
+            Error::NotRecording => code::NOT_RECORDING,
+            Error::UnknownMacro(..) => code::UNKNOWN_MACRO,
+            Error::ReservedOperator(..) => code::RESERVED_OPERATOR,
+        };
+        RpcError {
+            code,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// A JSON-RPC 2.0 server around a [`Calculator`], handling one message per
+/// line.
+///
+/// The methods mirror the web calculator's `WasmIosCalculator`.
+#[derive(Default)]
+pub struct Rpc {
+    calculator: Calculator,
+}
+
+impl Rpc {
+    /// Create a new server.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handles a line containing a request or a batch of requests, returning
+    /// the response to write if there is one.
+    ///
+    /// Notifications, requests without an id, have no response.
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        let response = match serde_json::from_str(line) {
+            Ok(Value::Array(batch)) if batch.is_empty() => Some(error_response(
+                Value::Null,
+                RpcError::new(code::INVALID_REQUEST, "The batch is empty."),
+            )),
+            Ok(Value::Array(batch)) => {
+                let responses: Vec<Value> = batch
+                    .into_iter()
+                    .filter_map(|request| self.handle_request(request))
+                    .collect();
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            Ok(request) => self.handle_request(request),
+            Err(error) => Some(error_response(
+                Value::Null,
+                RpcError::new(code::PARSE_ERROR, &error.to_string()),
+            )),
+        };
+        response.map(|response| response.to_string())
+    }
+
+    /// Handles a single request.
+    fn handle_request(&mut self, request: Value) -> Option<Value> {
+        let Value::Object(request) = request else {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(code::INVALID_REQUEST, "The request must be an object."),
+            ));
+        };
+
+        let id = request.get("id").cloned();
+        let (Some("2.0"), Some(Value::String(method))) = (
+            request.get("jsonrpc").and_then(Value::as_str),
+            request.get("method"),
+        ) else {
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                RpcError::new(
+                    code::INVALID_REQUEST,
+                    "The request must have a jsonrpc version of \"2.0\" and a method.",
+                ),
+            ));
+        };
+
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        let result = self.call(method, &params);
+
+        // Notifications have no response.
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    /// Calls a method.
+    fn call(&mut self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "buttonPressed" => {
+                let id = string_param(params, "id")?;
+                let key = self.calculator.key(id)?;
+                self.calculator.press(&key);
+                Ok(Value::Null)
+            }
+            "output" => Ok(json!(self.calculator.display().to_string())),
+            "activeOperator" => Ok(json!(self
+                .calculator
+                .active_operator()
+                .map(|operator| operator.id))),
+            "showAllClear" => Ok(json!(self.calculator.cleared())),
+            "evaluate" => {
+                let expression = string_param(params, "expression")?;
+                evaluate(expression).map(Value::String)
+            }
+            "state" => Ok(self.state()),
+            "reset" => {
+                self.calculator = Calculator::new();
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError::new(
+                code::METHOD_NOT_FOUND,
+                &format!("Unknown method '{}'.", method),
+            )),
+        }
+    }
+
+    /// Returns everything there is to display, with the names used by the
+    /// web calculator's `CalculatorState`.
+    fn state(&self) -> Value {
+        let view = self.calculator.view();
+        json!({
+            "display": view.display,
+            "expression": view.expression,
+            "activeOperator": view.active_operator,
+            "showAllClear": view.all_clear,
+            "clearLabel": if view.all_clear { "AC" } else { "C" },
+            "angleMode": match view.angle_mode {
+                AngleMode::Degrees => "degrees",
+                AngleMode::Radians => "radians",
+            },
+            "ariaLabel": view.spoken,
+            "memory": view.memory,
+            "error": view.error,
+        })
+    }
+}
+
+/// Evaluates keys on a new calculator, pressing equals at the end if it
+/// isn't already, and returns the display.
+fn evaluate(expression: &str) -> Result<String, RpcError> {
+    let mut calculator = Calculator::new();
+    let keys = calculator.parse_keys(expression)?;
+    for key in keys.iter() {
+        calculator.press(key);
+    }
+    if keys.last() != Some(&Key::Equals) {
+        calculator.press(&Key::Equals);
+    }
+    Ok(calculator.display().to_string())
+}
+
+/// Returns the only string parameter, given by position or by name.
+fn string_param<'a>(params: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+    let param = match params {
+        Value::Array(params) if params.len() == 1 => params.first(),
+        Value::Object(params) => params.get(name),
+        _ => None,
+    };
+    param.and_then(Value::as_str).ok_or_else(|| {
+        RpcError::new(
+            code::INVALID_PARAMS,
+            &format!("Expected a string parameter '{}'.", name),
+        )
+    })
+}
+
+/// Returns an error response.
+fn error_response(id: Value, error: RpcError) -> Value {
+    let mut response = Map::new();
+    response.insert(String::from("jsonrpc"), json!("2.0"));
+    response.insert(
+        String::from("error"),
+        json!({ "code": error.code, "message": error.message }),
+    );
+    response.insert(String::from("id"), id);
+    Value::Object(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{code, Rpc};
+    use serde_json::{json, Value};
+
+    /// Sends a request, returning the response.
+    fn call(rpc: &mut Rpc, request: Value) -> Value {
+        let response = rpc.handle(&request.to_string()).unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    /// Calls a method, returning its result.
+    fn result(rpc: &mut Rpc, method: &str, params: Value) -> Value {
+        let response = call(
+            rpc,
+            json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 }),
+        );
+        assert_eq!(response["error"], Value::Null, "Should succeed.");
+        response["result"].clone()
+    }
+
+    /// Calls a method, returning its error code.
+    fn error_code(rpc: &mut Rpc, method: &str, params: Value) -> Value {
+        let response = call(
+            rpc,
+            json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 }),
+        );
+        response["error"]["code"].clone()
+    }
+
+    #[test]
+    fn button_pressed() {
+        let mut rpc = Rpc::new();
+        for id in ["1", "2", "+"] {
+            assert_eq!(result(&mut rpc, "buttonPressed", json!([id])), Value::Null);
+        }
+        assert_eq!(result(&mut rpc, "output", Value::Null), json!("12"));
+        assert_eq!(result(&mut rpc, "activeOperator", Value::Null), json!("+"));
+        assert_eq!(result(&mut rpc, "showAllClear", Value::Null), json!(false));
+
+        result(&mut rpc, "buttonPressed", json!({ "id": "AC" }));
+        assert_eq!(result(&mut rpc, "output", Value::Null), json!("0"));
+        assert_eq!(result(&mut rpc, "showAllClear", Value::Null), json!(true));
+    }
+
+    #[test]
+    fn evaluate() {
+        let mut rpc = Rpc::new();
+        assert_eq!(result(&mut rpc, "evaluate", json!(["1+2*3"])), json!("7"));
+        assert_eq!(
+            result(&mut rpc, "output", Value::Null),
+            json!("0"),
+            "Should not change the calculator."
+        );
+    }
+
+    #[test]
+    fn state_and_reset() {
+        let mut rpc = Rpc::new();
+        result(&mut rpc, "buttonPressed", json!(["5"]));
+        result(&mut rpc, "buttonPressed", json!(["*"]));
+
+        let state = result(&mut rpc, "state", Value::Null);
+        assert_eq!(state["display"], "5");
+        assert_eq!(state["expression"], "5 ×");
+        assert_eq!(state["clearLabel"], "C");
+        assert_eq!(state["ariaLabel"], "five times");
+
+        result(&mut rpc, "reset", Value::Null);
+        let state = result(&mut rpc, "state", Value::Null);
+        assert_eq!(state["display"], "0");
+        assert_eq!(state["activeOperator"], Value::Null);
+    }
+
+    #[test]
+    fn errors() {
+        let mut rpc = Rpc::new();
+        assert_eq!(
+            error_code(&mut rpc, "buttonPressed", json!(["?"])),
+            code::UNKNOWN_KEY
+        );
+        assert_eq!(
+            error_code(&mut rpc, "evaluate", json!(["{AC"])),
+            code::UNKNOWN_KEY
+        );
+        assert_eq!(
+            error_code(&mut rpc, "buttonPressed", json!([7])),
+            code::INVALID_PARAMS
+        );
+        assert_eq!(
+            error_code(&mut rpc, "press", Value::Null),
+            code::METHOD_NOT_FOUND
+        );
+
+        let response = call(&mut rpc, json!({ "method": "output", "id": 2 }));
+        assert_eq!(response["error"]["code"], code::INVALID_REQUEST);
+        assert_eq!(response["id"], 2);
+
+        let response: Value = serde_json::from_str(&rpc.handle("{").unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], code::PARSE_ERROR);
+        assert_eq!(response["id"], Value::Null);
+    }
+
+    #[test]
+    fn notifications_and_batches() {
+        let mut rpc = Rpc::new();
+        let notification = json!({ "jsonrpc": "2.0", "method": "buttonPressed", "params": ["4"] });
+        assert_eq!(
+            rpc.handle(&notification.to_string()),
+            None,
+            "Should not respond to notifications."
+        );
+
+        let batch = json!([
+            notification,
+            { "jsonrpc": "2.0", "method": "output", "id": 1 },
+            { "jsonrpc": "2.0", "method": "showAllClear", "id": 2 },
+        ]);
+        assert_eq!(
+            call(&mut rpc, batch),
+            json!([
+                { "jsonrpc": "2.0", "result": "44", "id": 1 },
+                { "jsonrpc": "2.0", "result": false, "id": 2 },
+            ])
+        );
+    }
+}
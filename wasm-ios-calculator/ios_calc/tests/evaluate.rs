@@ -0,0 +1,23 @@
+//! Tests `ios-calc -e`.
+use std::process::Command;
+
+/// Runs `ios-calc -e` with the keys, returning what it prints.
+fn evaluate(keys: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_ios-calc"))
+        .args(["-e", keys])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "Should evaluate {:?}.", keys);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn presses_equals() {
+    assert_eq!(evaluate("1+2="), "3\n");
+    assert_eq!(
+        evaluate("1+2"),
+        "3\n",
+        "Should press equals when it is missing."
+    );
+    assert_eq!(evaluate("2*3="), evaluate("2*3"));
+}
@@ -0,0 +1,49 @@
+//! Tests `ios-calc --rpc` as another process would use it.
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Command, Stdio},
+};
+
+#[test]
+fn rpc_over_stdio() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ios-calc"))
+        .arg("--rpc")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+    // Responses should arrive before the input is closed.
+    let mut call = |request: &str| {
+        writeln!(stdin, "{}", request).unwrap();
+        let mut response = String::new();
+        stdout.read_line(&mut response).unwrap();
+        serde_json::from_str::<Value>(&response).unwrap()
+    };
+
+    assert_eq!(
+        call(r#"{"jsonrpc": "2.0", "method": "buttonPressed", "params": ["9"], "id": 1}"#),
+        json!({ "jsonrpc": "2.0", "result": null, "id": 1 })
+    );
+    assert_eq!(
+        call(r#"{"jsonrpc": "2.0", "method": "output", "id": 2}"#),
+        json!({ "jsonrpc": "2.0", "result": "9", "id": 2 })
+    );
+    assert_eq!(
+        call(r#"{"jsonrpc": "2.0", "method": "buttonPressed", "params": ["?"], "id": 3}"#),
+        json!({
+            "jsonrpc": "2.0",
+            "error": { "code": 4, "message": "Unknown key \"?\"." },
+            "id": 3
+        })
+    );
+
+    drop(stdin);
+    assert!(
+        child.wait().unwrap().success(),
+        "Should exit when the input ends."
+    );
+}
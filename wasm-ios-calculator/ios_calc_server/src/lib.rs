@@ -0,0 +1,293 @@
+//! # ios-calc-server
+//!
+//! A local HTTP JSON API hosting named [`Calculator`] sessions in memory.
+//!
+//! | Method   | Path                    | Body                      | Response          |
+//! |----------|-------------------------|---------------------------|-------------------|
+//! | `GET`    | `/sessions`             |                           | Session names     |
+//! | `POST`   | `/sessions`             | `{"name": "a"}`           | Session, 201      |
+//! | `GET`    | `/sessions/{name}`      |                           | Session           |
+//! | `DELETE` | `/sessions/{name}`      |                           | 204               |
+//! | `POST`   | `/sessions/{name}/keys` | `{"keys": "1+2="}`        | View              |
+//! | `POST`   | `/evaluate`             | `{"expression": "1+2*3"}` | Result            |
+//!
+//! Keys use the [`parse_keys`](ios_calculator::parse_keys) format. Errors
+//! are returned as `{"error": "..."}` with a 4xx status.
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use ios_calculator::{AngleMode, Calculator, Error, Key, ViewModel};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+use tokio::net::TcpListener;
+
+/// Keys pressed in a session.
+#[derive(Serialize, Clone)]
+pub struct Entry {
+    /// The keys as they were sent.
+    pub keys: String,
+    /// The display after the keys were pressed.
+    pub display: String,
+}
+
+/// A calculator and the keys pressed on it.
+#[derive(Default)]
+struct Session {
+    calculator: Calculator,
+    history: Vec<Entry>,
+}
+
+/// The sessions hosted by the server, by name.
+#[derive(Clone, Default)]
+struct Sessions(Arc<Mutex<BTreeMap<String, Session>>>);
+
+impl Sessions {
+    /// Locks the sessions.
+    ///
+    /// A request that panicked leaves the sessions as they were, so they
+    /// remain usable.
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, Session>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Everything there is to display, see [`ViewModel`].
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct View {
+    /// The text shown on the display.
+    pub display: String,
+    /// The calculation entered so far, using operator glyphs.
+    pub expression: String,
+    /// The expression as it would be read aloud in English.
+    pub spoken: String,
+    /// The id of the operator that is highlighted, if any.
+    pub active_operator: Option<char>,
+    /// Should the clear key read 'AC' rather than 'C'?
+    pub all_clear: bool,
+    /// Is the last operation remembered for repeated equals?
+    pub memory: bool,
+    /// Is the display showing an error?
+    pub error: bool,
+    /// The unit used for angles, "degrees" or "radians".
+    pub angle_mode: &'static str,
+}
+
+impl From<ViewModel> for View {
+    fn from(view: ViewModel) -> Self {
+        View {
+            display: view.display,
+            expression: view.expression,
+            spoken: view.spoken,
+            active_operator: view.active_operator,
+            all_clear: view.all_clear,
+            memory: view.memory,
+            error: view.error,
+            angle_mode: match view.angle_mode {
+                AngleMode::Degrees => "degrees",
+                AngleMode::Radians => "radians",
+            },
+        }
+    }
+}
+
+/// A session's state.
+#[derive(Serialize)]
+pub struct SessionState {
+    /// The session's name.
+    pub name: String,
+    /// Everything there is to display.
+    pub view: View,
+    /// The keys pressed so far.
+    pub history: Vec<Entry>,
+}
+
+impl SessionState {
+    /// Captures the state of a named session.
+    fn new(name: &str, session: &Session) -> Self {
+        SessionState {
+            name: name.to_string(),
+            view: session.calculator.view().into(),
+            history: session.history.clone(),
+        }
+    }
+}
+
+/// The result of evaluating an expression.
+#[derive(Serialize)]
+pub struct Evaluation {
+    /// The result as it is displayed.
+    pub display: String,
+    /// The numeric value of the result, or null if it is an error.
+    pub value: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct CreateSession {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct PressKeys {
+    keys: String,
+}
+
+#[derive(Deserialize)]
+struct Evaluate {
+    expression: String,
+}
+
+/// An error returned by the API.
+#[derive(Debug)]
+enum ApiError {
+    /// No session has the name.
+    UnknownSession(String),
+    /// A session already has the name.
+    SessionExists(String),
+    /// The session name is empty.
+    EmptyName,
+    /// The calculator reported an error, such as an unknown key.
+    Calculator(Error),
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::Calculator(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::UnknownSession(name) => (
+                StatusCode::NOT_FOUND,
+                format!("Unknown session \"{}\".", name),
+            ),
+            ApiError::SessionExists(name) => (
+                StatusCode::CONFLICT,
+                format!("Session \"{}\" already exists.", name),
+            ),
+            ApiError::EmptyName => (
+                StatusCode::BAD_REQUEST,
+                String::from("Session names can't be empty."),
+            ),
+            ApiError::Calculator(error) => (StatusCode::BAD_REQUEST, error.to_string()),
+        };
+        let body = BTreeMap::from([("error", message)]);
+        (status, Json(body)).into_response()
+    }
+}
+
+/// Returns the names of the sessions.
+async fn list_sessions(State(sessions): State<Sessions>) -> Json<Vec<String>> {
+    Json(sessions.lock().keys().cloned().collect())
+}
+
+/// Creates a session with a new calculator.
+async fn create_session(
+    State(sessions): State<Sessions>,
+    Json(request): Json<CreateSession>,
+) -> Result<(StatusCode, Json<SessionState>), ApiError> {
+    if request.name.is_empty() {
+        return Err(ApiError::EmptyName);
+    }
+
+    let mut sessions = sessions.lock();
+    if sessions.contains_key(&request.name) {
+        return Err(ApiError::SessionExists(request.name));
+    }
+    let session = sessions.entry(request.name.clone()).or_default();
+    Ok((
+        StatusCode::CREATED,
+        Json(SessionState::new(&request.name, session)),
+    ))
+}
+
+/// Returns a session's view and history.
+async fn get_session(
+    State(sessions): State<Sessions>,
+    Path(name): Path<String>,
+) -> Result<Json<SessionState>, ApiError> {
+    let sessions = sessions.lock();
+    let session = sessions
+        .get(&name)
+        .ok_or_else(|| ApiError::UnknownSession(name.clone()))?;
+    Ok(Json(SessionState::new(&name, session)))
+}
+
+/// Deletes a session.
+async fn delete_session(
+    State(sessions): State<Sessions>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    match sessions.lock().remove(&name) {
+        Some(..) => Ok(StatusCode::NO_CONTENT),
+        None => Err(ApiError::UnknownSession(name)),
+    }
+}
+
+/// Presses keys in a session, returning its view.
+///
+/// No keys are pressed if any of them is unknown.
+async fn press_keys(
+    State(sessions): State<Sessions>,
+    Path(name): Path<String>,
+    Json(request): Json<PressKeys>,
+) -> Result<Json<View>, ApiError> {
+    let mut sessions = sessions.lock();
+    let session = sessions
+        .get_mut(&name)
+        .ok_or_else(|| ApiError::UnknownSession(name.clone()))?;
+
+    let keys = session.calculator.parse_keys(&request.keys)?;
+    for key in keys.iter() {
+        session.calculator.press(key);
+    }
+    session.history.push(Entry {
+        keys: request.keys,
+        display: session.calculator.display().to_string(),
+    });
+    Ok(Json(session.calculator.view().into()))
+}
+
+/// Evaluates an expression on a new calculator, pressing equals at the end
+/// if it isn't already.
+async fn evaluate(Json(request): Json<Evaluate>) -> Result<Json<Evaluation>, ApiError> {
+    let mut calculator = Calculator::new();
+    let keys = calculator.parse_keys(&request.expression)?;
+    for key in keys.iter() {
+        calculator.press(key);
+    }
+    if keys.last() != Some(&Key::Equals) {
+        calculator.press(&Key::Equals);
+    }
+
+    let display = calculator.display();
+    Ok(Json(Evaluation {
+        display: display.to_string(),
+        value: (!display.is_error()).then(|| display.value()),
+    }))
+}
+
+/// Returns the API's routes, with no sessions.
+pub fn router() -> Router {
+    Router::new()
+        .route("/sessions", get(list_sessions).post(create_session))
+        .route("/sessions/{name}", get(get_session).delete(delete_session))
+        .route("/sessions/{name}/keys", post(press_keys))
+        .route("/evaluate", post(evaluate))
+        .with_state(Sessions::default())
+}
+
+/// Serves the API on a listener until the process exits.
+pub async fn serve(listener: TcpListener) -> io::Result<()> {
+    axum::serve(listener, router()).await
+}
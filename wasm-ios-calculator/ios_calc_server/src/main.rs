@@ -0,0 +1,51 @@
+//! # ios-calc-server
+//!
+//! Serves the calculator's HTTP JSON API, see [`ios_calc_server`].
+use std::{env, process::ExitCode};
+use tokio::net::TcpListener;
+
+/// The address served on when none is given.
+const DEFAULT_ADDRESS: &str = "127.0.0.1:3000";
+
+/// The usage text printed for `--help`.
+const USAGE: &str = "\
+Usage:
+  ios-calc-server              Serve the API on 127.0.0.1:3000.
+  ios-calc-server <address>    Serve the API on an address, e.g. `127.0.0.1:8080`.";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let address = match args.as_slice() {
+        [] => DEFAULT_ADDRESS,
+        [flag] if flag == "-h" || flag == "--help" => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        [address] if !address.starts_with('-') => address,
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let listener = match TcpListener::bind(address).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("Couldn't listen on {}: {}", address, error);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Ok(address) = listener.local_addr() {
+        println!("Listening on http://{}", address);
+    }
+
+    match ios_calc_server::serve(listener).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
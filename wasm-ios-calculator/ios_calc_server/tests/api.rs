@@ -0,0 +1,189 @@
+//! Tests the API over HTTP on `127.0.0.1`.
+use serde_json::{json, Value};
+use std::{net, thread};
+use tokio::{net::TcpListener, runtime};
+use ureq::Agent;
+
+/// A client for a server running on a background thread.
+struct Client {
+    agent: Agent,
+    url: String,
+}
+
+impl Client {
+    /// Starts a server on a free port.
+    fn start() -> Self {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        listener.set_nonblocking(true).unwrap();
+
+        thread::spawn(move || {
+            let runtime = runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let listener = TcpListener::from_std(listener).unwrap();
+                ios_calc_server::serve(listener).await.unwrap();
+            });
+        });
+
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Client { agent, url }
+    }
+
+    /// Sends a request, returning the status and JSON body.
+    fn request(&self, method: &str, path: &str, body: Option<Value>) -> (u16, Value) {
+        let url = format!("{}{}", self.url, path);
+        let mut response = match (method, body) {
+            ("GET", None) => self.agent.get(&url).call(),
+            ("DELETE", None) => self.agent.delete(&url).call(),
+            ("POST", Some(body)) => self.agent.post(&url).send_json(body),
+            _ => panic!("Unsupported request {} {}", method, path),
+        }
+        .unwrap();
+
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string().unwrap();
+        let body = match body.as_str() {
+            "" => Value::Null,
+            body => serde_json::from_str(body).unwrap(),
+        };
+        (status, body)
+    }
+
+    /// Creates a session, checking that it succeeds.
+    fn create(&self, name: &str) {
+        let (status, _) = self.request("POST", "/sessions", Some(json!({ "name": name })));
+        assert_eq!(status, 201, "Should create the session.");
+    }
+
+    /// Presses keys in a session.
+    fn press(&self, name: &str, keys: &str) -> (u16, Value) {
+        let path = format!("/sessions/{}/keys", name);
+        self.request("POST", &path, Some(json!({ "keys": keys })))
+    }
+}
+
+#[test]
+fn create_session() {
+    let client = Client::start();
+    let (status, body) = client.request("POST", "/sessions", Some(json!({ "name": "a" })));
+
+    assert_eq!(status, 201);
+    assert_eq!(body["name"], "a");
+    assert_eq!(body["view"]["display"], "0");
+    assert_eq!(body["view"]["allClear"], true);
+    assert_eq!(body["history"], json!([]));
+
+    let (status, body) = client.request("POST", "/sessions", Some(json!({ "name": "a" })));
+    assert_eq!(status, 409, "Should not replace an existing session.");
+    assert_eq!(body["error"], "Session \"a\" already exists.");
+
+    let (status, _) = client.request("POST", "/sessions", Some(json!({ "name": "" })));
+    assert_eq!(status, 400, "Should require a name.");
+}
+
+#[test]
+fn press_keys() {
+    let client = Client::start();
+    client.create("a");
+
+    let (status, view) = client.press("a", "12+3*");
+    assert_eq!(status, 200);
+    assert_eq!(view["display"], "3");
+    assert_eq!(view["expression"], "12 + 3 ×");
+    assert_eq!(view["activeOperator"], "*");
+    assert_eq!(view["allClear"], false);
+    assert_eq!(view["angleMode"], "degrees");
+
+    let (_, view) = client.press("a", "4=");
+    assert_eq!(view["display"], "24");
+    assert_eq!(view["memory"], true);
+
+    let (status, body) = client.press("a", "1?");
+    assert_eq!(status, 400, "Should reject unknown keys.");
+    assert_eq!(body["error"], "Unknown key \"?\".");
+    let (_, session) = client.request("GET", "/sessions/a", None);
+    assert_eq!(
+        session["view"]["display"], "24",
+        "Should not press any keys when one is unknown."
+    );
+}
+
+#[test]
+fn history() {
+    let client = Client::start();
+    client.create("a");
+    client.press("a", "1+2=");
+    client.press("a", "*3=");
+
+    let (status, session) = client.request("GET", "/sessions/a", None);
+    assert_eq!(status, 200);
+    assert_eq!(
+        session["history"],
+        json!([
+            { "keys": "1+2=", "display": "3" },
+            { "keys": "*3=", "display": "9" },
+        ])
+    );
+}
+
+#[test]
+fn sessions_are_independent() {
+    let client = Client::start();
+    client.create("a");
+    client.create("b");
+    client.press("a", "5");
+    client.press("b", "1/0=");
+
+    let (_, sessions) = client.request("GET", "/sessions", None);
+    assert_eq!(sessions, json!(["a", "b"]));
+
+    let (_, a) = client.request("GET", "/sessions/a", None);
+    let (_, b) = client.request("GET", "/sessions/b", None);
+    assert_eq!(a["view"]["display"], "5");
+    assert_eq!(b["view"]["display"], "Error");
+    assert_eq!(b["view"]["error"], true);
+}
+
+#[test]
+fn delete_session() {
+    let client = Client::start();
+    client.create("a");
+
+    let (status, _) = client.request("DELETE", "/sessions/a", None);
+    assert_eq!(status, 204);
+
+    let (status, body) = client.request("GET", "/sessions/a", None);
+    assert_eq!(status, 404);
+    assert_eq!(body["error"], "Unknown session \"a\".");
+
+    let (status, _) = client.press("a", "1");
+    assert_eq!(status, 404, "Should not press keys in a deleted session.");
+}
+
+#[test]
+fn evaluate() {
+    let client = Client::start();
+
+    let (status, result) =
+        client.request("POST", "/evaluate", Some(json!({ "expression": "1+2*3" })));
+    assert_eq!(status, 200);
+    assert_eq!(result, json!({ "display": "7", "value": 7.0 }));
+
+    let (_, result) = client.request("POST", "/evaluate", Some(json!({ "expression": "2*3=" })));
+    assert_eq!(
+        result["display"], "6",
+        "Should not repeat the last operation when the expression ends with equals."
+    );
+
+    let (_, result) = client.request("POST", "/evaluate", Some(json!({ "expression": "1/0" })));
+    assert_eq!(result, json!({ "display": "Error", "value": null }));
+
+    let (status, _) = client.request("POST", "/evaluate", Some(json!({ "expression": "{x" })));
+    assert_eq!(status, 400);
+}
@@ -0,0 +1,263 @@
+use ios_calculator::Calculator;
+use ratatui::{
+    crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    layout::{Alignment, Constraint, Layout, Position, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, BorderType, Paragraph},
+    Frame,
+};
+
+/// The ids of the keypad's buttons, laid out as on iOS.
+///
+/// A button spans several columns when its id is repeated.
+const KEYPAD: [[&str; 4]; 5] = [
+    ["c", "±", "%", "/"],
+    ["7", "8", "9", "*"],
+    ["4", "5", "6", "-"],
+    ["1", "2", "3", "+"],
+    ["0", "0", ".", "="],
+];
+
+/// The colour of the operator buttons.
+const ORANGE: Color = Color::Rgb(255, 159, 10);
+
+/// A full-screen calculator driven by a [`Calculator`].
+#[derive(Default)]
+pub struct App {
+    calculator: Calculator,
+    /// The area of each button as it was last drawn, used to handle clicks.
+    buttons: Vec<(Rect, &'static str)>,
+    /// Should the app exit?
+    quit: bool,
+}
+
+impl App {
+    /// Create a new app.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks if the app should exit.
+    pub fn quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Presses the calculator key with the given id.
+    fn press(&mut self, id: &str) {
+        if let Ok(key) = self.calculator.key(id) {
+            self.calculator.press(&key);
+        }
+    }
+
+    /// Handles a keyboard or mouse event.
+    pub fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => self.handle_key(key),
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) => {
+                let position = Position::new(*column, *row);
+                let id = self
+                    .buttons
+                    .iter()
+                    .find(|(area, _)| area.contains(position))
+                    .map(|(_, id)| *id);
+
+                if let Some(id) = id {
+                    self.press(id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Maps a key press onto a calculator key.
+    fn handle_key(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc | KeyCode::Delete | KeyCode::Char('c') => self.press("c"),
+            KeyCode::Enter => self.press("="),
+            KeyCode::Char('n') | KeyCode::Char('_') => self.press("±"),
+            KeyCode::Char('x') => self.press("*"),
+            KeyCode::Char(char) => self.press(&char.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Returns the label shown on a button.
+    fn label(&self, id: &str) -> String {
+        match id {
+            "c" if self.calculator.cleared() => String::from("AC"),
+            "c" => String::from("C"),
+            _ => match self.calculator.operator(id) {
+                Some(operator) => operator.glyph,
+                None => id.to_string(),
+            },
+        }
+    }
+
+    /// Returns the style of a button.
+    fn style(&self, id: &str) -> Style {
+        let active = self
+            .calculator
+            .active_operator()
+            .is_some_and(|operator| operator.id.to_string() == id);
+
+        match id {
+            _ if active => Style::new().fg(ORANGE).bg(Color::White),
+            "=" => Style::new().fg(Color::White).bg(ORANGE),
+            _ if self.calculator.operator(id).is_some() => Style::new().fg(Color::White).bg(ORANGE),
+            "c" | "±" | "%" => Style::new().fg(Color::Black).bg(Color::Gray),
+            _ => Style::new().fg(Color::White).bg(Color::DarkGray),
+        }
+    }
+
+    /// Draws the display and keypad.
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [expression_area, display_area, keypad_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(
+            Paragraph::new(self.calculator.expression())
+                .alignment(Alignment::Right)
+                .fg(Color::Gray),
+            expression_area,
+        );
+        frame.render_widget(
+            Paragraph::new(self.calculator.display().to_string())
+                .alignment(Alignment::Right)
+                .bold()
+                .block(Block::bordered().border_type(BorderType::Rounded)),
+            display_area,
+        );
+
+        self.buttons.clear();
+        let rows = Layout::vertical([Constraint::Ratio(1, KEYPAD.len() as u32); KEYPAD.len()])
+            .split(keypad_area);
+
+        for (ids, row) in KEYPAD.iter().zip(rows.iter()) {
+            let cells = Layout::horizontal([Constraint::Ratio(1, 4); 4]).split(*row);
+
+            for (id, cell) in ids.iter().zip(cells.iter()) {
+                // Widen a repeated button across its columns.
+                match self.buttons.last_mut() {
+                    Some((area, last)) if last == id && area.y == cell.y => {
+                        *area = area.union(*cell);
+                    }
+                    _ => self.buttons.push((*cell, id)),
+                }
+            }
+        }
+
+        for (area, id) in &self.buttons {
+            frame.render_widget(
+                Paragraph::new(self.label(id))
+                    .alignment(Alignment::Center)
+                    .block(Block::bordered().border_type(BorderType::Rounded))
+                    .style(self.style(id)),
+                *area,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, crossterm::event::KeyModifiers, Terminal};
+
+    /// Draws the app onto a test terminal, returning the terminal.
+    fn draw(app: &mut App) -> Terminal<TestBackend> {
+        let mut terminal = Terminal::new(TestBackend::new(40, 24)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        terminal
+    }
+
+    /// Clicks in the middle of the button with the given id.
+    fn click(app: &mut App, id: &str) {
+        let (area, _) = app
+            .buttons
+            .iter()
+            .find(|(_, button)| *button == id)
+            .unwrap();
+        let event = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: area.x + area.width / 2,
+            row: area.y + area.height / 2,
+            modifiers: KeyModifiers::NONE,
+        });
+        app.handle_event(&event);
+    }
+
+    fn type_char(app: &mut App, char: char) {
+        app.handle_event(&Event::Key(KeyEvent::from(KeyCode::Char(char))));
+    }
+
+    #[test]
+    fn mouse_input() {
+        let mut app = App::new();
+        draw(&mut app);
+        for id in ["7", "*", "0", "="] {
+            click(&mut app, id);
+        }
+        assert_eq!(app.calculator.display().to_string(), "0");
+
+        click(&mut app, "c");
+        for id in ["1", "2", "+", "3", "="] {
+            click(&mut app, id);
+        }
+        assert_eq!(app.calculator.display().to_string(), "15");
+    }
+
+    #[test]
+    fn keyboard_input() {
+        let mut app = App::new();
+        for char in "12x3".chars() {
+            type_char(&mut app, char);
+        }
+        app.handle_event(&Event::Key(KeyEvent::from(KeyCode::Enter)));
+        assert_eq!(app.calculator.display().to_string(), "36");
+
+        type_char(&mut app, 'q');
+        assert!(app.quit());
+    }
+
+    #[test]
+    fn wide_zero() {
+        let mut app = App::new();
+        draw(&mut app);
+        let (zero, _) = app.buttons.iter().find(|(_, id)| *id == "0").unwrap();
+        let (one, _) = app.buttons.iter().find(|(_, id)| *id == "1").unwrap();
+        assert_eq!(zero.width, one.width * 2, "Should span two columns.");
+    }
+
+    #[test]
+    fn highlights_active_operator() {
+        let mut app = App::new();
+        type_char(&mut app, '+');
+        let terminal = draw(&mut app);
+
+        let (area, _) = app.buttons.iter().find(|(_, id)| *id == "+").unwrap();
+        let cell = &terminal.backend().buffer()[(area.x + 1, area.y + 1)];
+        assert_eq!(cell.bg, Color::White);
+    }
+
+    #[test]
+    fn clear_label() {
+        let mut app = App::new();
+        assert_eq!(app.label("c"), "AC");
+        type_char(&mut app, '5');
+        assert_eq!(app.label("c"), "C");
+    }
+}
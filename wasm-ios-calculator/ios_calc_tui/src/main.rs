@@ -0,0 +1,39 @@
+//! # ios-calc-tui
+//!
+//! A full-screen terminal calculator with the iOS keypad layout.
+//!
+//! Buttons can be clicked with the mouse or typed on the keyboard:
+//! `Enter` for equals, `Esc` to clear, `n` to change sign and `q` to quit.
+mod app;
+
+use app::App;
+use ratatui::{
+    crossterm::{
+        event::{self, DisableMouseCapture, EnableMouseCapture},
+        execute,
+    },
+    DefaultTerminal,
+};
+use std::io::{self, stdout};
+
+fn main() -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal);
+
+    // Restore the terminal whether or not the calculator ran successfully.
+    let disabled = execute!(stdout(), DisableMouseCapture);
+    ratatui::restore();
+    result.and(disabled)
+}
+
+/// Runs the calculator until it is quit.
+fn run(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    execute!(stdout(), EnableMouseCapture)?;
+
+    let mut app = App::new();
+    while !app.quit() {
+        terminal.draw(|frame| app.draw(frame))?;
+        app.handle_event(&event::read()?);
+    }
+    Ok(())
+}
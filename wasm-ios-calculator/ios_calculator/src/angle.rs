@@ -0,0 +1,35 @@
+/// The unit used for angles.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum AngleMode {
+    /// Angles are measured in degrees.
+    #[default]
+    Degrees,
+    /// Angles are measured in radians.
+    Radians,
+}
+
+impl AngleMode {
+    /// Converts an angle in this unit into radians.
+    pub fn to_radians(&self, angle: f64) -> f64 {
+        match self {
+            AngleMode::Degrees => angle.to_radians(),
+            AngleMode::Radians => angle,
+        }
+    }
+
+    /// Converts an angle in radians into this unit.
+    pub fn from_radians(&self, angle: f64) -> f64 {
+        match self {
+            AngleMode::Degrees => angle.to_degrees(),
+            AngleMode::Radians => angle,
+        }
+    }
+
+    /// Returns the other angle mode.
+    pub fn toggled(&self) -> Self {
+        match self {
+            AngleMode::Degrees => AngleMode::Radians,
+            AngleMode::Radians => AngleMode::Degrees,
+        }
+    }
+}
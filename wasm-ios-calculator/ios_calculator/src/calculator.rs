@@ -1,93 +1,308 @@
-use crate::{number::Number, operator::Operator};
+use crate::{
+    angle::AngleMode,
+    engine::{self, Engine},
+    error::Error,
+    function::Function,
+    key::{self, Key},
+    number::{DisplayMode, Number},
+    operator::Operator,
+    registry::OperatorRegistry,
+    speech::speak_operator,
+    storage::Heap,
+    view::ViewModel,
+};
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
 
 /// A token that can be entered into the Calculator buffer.
-///
-/// Can be one of:
-/// - [`Number`] - A representation of a number.
-/// - [`Operator`] - A representation of a mathematical operator.
-#[derive(Debug, PartialEq, Clone)]
-pub enum Token {
-    Number(Number),
-    Operator(Operator),
-}
+pub(crate) type Token = engine::Token<Heap>;
 
 /// A simple calulator.
+#[derive(Debug, Clone)]
 pub struct Calculator {
-    /// An vector of [`Token`] that can be parsed to calulate the output.
-    buffer: Vec<Token>,
-    /// The index into the buffer.
-    display_index: usize,
-    /// Is the calculator's buffer cleared?
-    cleared: bool,
-    /// Is the calculator in an editing state?
-    editing: bool,
+    /// The state of the calculator, stored on the heap.
+    engine: Engine<Heap>,
+    /// The operators available to the calculator.
+    operators: OperatorRegistry,
+    /// The keys pressed since recording a macro started.
+    recording: Option<Vec<Key>>,
+    /// The saved macros, by name.
+    macros: BTreeMap<String, Vec<Key>>,
+}
+
+impl Default for Calculator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Calculator {
+    /// The smallest number of significant figures that fit on the display,
+    /// leaving room for a digit after the decimal point.
+    pub const MIN_DISPLAY_WIDTH: u8 = 2;
+
+    /// The largest number of significant figures that fit on the display,
+    /// as every number with that many digits has an exact `f64` value.
+    pub const MAX_DISPLAY_WIDTH: u8 = 15;
+
     /// Create a new calculator.
     pub fn new() -> Self {
         Calculator {
-            buffer: Vec::from([Token::Number(Number::from(0.))]),
-            display_index: 0,
-            cleared: true,
-            editing: false,
+            engine: Engine::new(),
+            operators: OperatorRegistry::default(),
+            recording: None,
+            macros: BTreeMap::new(),
         }
     }
 
     /// Getter function for the cleared property.
     pub fn cleared(&self) -> bool {
-        self.cleared
+        self.engine.cleared()
+    }
+
+    /// Getter function for the complex mode property.
+    pub fn complex_mode(&self) -> bool {
+        self.engine.complex_mode()
+    }
+
+    /// Sets whether the calculator operates on complex values.
+    ///
+    /// In real mode, operations with no real result (such as the square root
+    /// of a negative number) produce an error.
+    pub fn set_complex_mode(&mut self, complex: bool) {
+        self.engine.set_complex_mode(complex);
+    }
+
+    /// Getter function for the polar property.
+    pub fn polar(&self) -> bool {
+        self.engine.polar()
+    }
+
+    /// Toggles the display of complex values between rectangular and polar
+    /// notation.
+    pub fn toggle_polar(&mut self) {
+        self.engine.toggle_polar();
+    }
+
+    /// Getter function for the angle mode property.
+    pub fn angle_mode(&self) -> AngleMode {
+        self.engine.angle_mode()
+    }
+
+    /// Sets the unit used for angles.
+    pub fn set_angle_mode(&mut self, angle_mode: AngleMode) {
+        self.engine.set_angle_mode(angle_mode);
+    }
+
+    /// Getter function for the display mode property.
+    pub fn display_mode(&self) -> DisplayMode {
+        self.engine.display_mode()
+    }
+
+    /// Sets the mode used to display real values.
+    pub fn set_display_mode(&mut self, display_mode: DisplayMode) {
+        self.engine.set_display_mode(display_mode);
+    }
+
+    /// Seeds the generator used for random numbers.
+    ///
+    /// The same seed will always produce the same sequence of random numbers.
+    /// Otherwise the generator is seeded from entropy, or from zero without
+    /// the `std` feature.
+    pub fn seed_random(&mut self, seed: u64) {
+        self.engine.seed_random(seed);
+    }
+
+    /// Returns the operators available to the calculator.
+    pub fn operators(&self) -> &OperatorRegistry {
+        &self.operators
+    }
+
+    /// Registers an operator, replacing any operator with the same identifier.
+    ///
+    /// Fails if the identifier is reserved for another key, see
+    /// [`OperatorRegistry::register`].
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::{Associativity, Calculator, Operator};
+    ///
+    /// let mut calc = Calculator::new();
+    /// calc.register_operator(Operator::new('m', "mod", 2, Associativity::Left, |x, y| {
+    ///     x.rem_euclid(y)
+    /// }))
+    /// .unwrap();
+    ///
+    /// calc.submit_number(7);
+    /// calc.submit_operator(calc.operator("m").unwrap());
+    /// calc.submit_number(4);
+    /// calc.submit_equals();
+    ///
+    /// assert_eq!(calc.display().value(), 3.);
+    /// ```
+    pub fn register_operator(&mut self, operator: Operator) -> Result<Option<Operator>, Error> {
+        self.operators.register(operator)
+    }
+
+    /// Returns the registered operator with a string identifier if it exists.
+    pub fn operator(&self, id: &str) -> Option<Operator> {
+        self.operators.get(id).cloned()
+    }
+
+    /// Returns the key with a string identifier, resolving operators through
+    /// the calculator's registry.
+    pub fn key(&self, id: &str) -> Result<Key, Error> {
+        Key::from_id(id, &self.operators)
+    }
+
+    /// Parses a sequence of keys, resolving operators through the
+    /// calculator's registry. See [`parse_keys`](crate::parse_keys) for the
+    /// format.
+    pub fn parse_keys(&self, input: &str) -> Result<Vec<Key>, Error> {
+        key::parse_keys(input, &self.operators)
+    }
+
+    /// Presses a key.
+    ///
+    /// The key is recorded if a macro is being recorded.
+    pub fn press(&mut self, key: &Key) {
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push(key.clone());
+        }
+
+        self.engine.press(key);
+    }
+
+    /// Presses a sequence of keys, returning the formatted output after each
+    /// key is pressed.
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::Calculator;
+    ///
+    /// let mut calc = Calculator::new();
+    /// let keys = calc.parse_keys("1.2+2==").unwrap();
+    ///
+    /// assert_eq!(
+    ///     calc.replay(&keys),
+    ///     vec!["1", "1.", "1.2", "1.2", "2", "3.2", "5.2"],
+    /// );
+    /// ```
+    pub fn replay(&mut self, keys: &[Key]) -> Vec<String> {
+        keys.iter()
+            .map(|key| {
+                self.press(key);
+                self.display().to_string()
+            })
+            .collect()
+    }
+
+    /// Starts recording a macro.
+    ///
+    /// Keys pressed with [`Calculator::press`] are recorded until the macro is
+    /// saved with [`Calculator::stop_recording`]. Starting again discards
+    /// any keys recorded so far.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Is a macro being recorded?
+    pub fn recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Stops recording a macro and saves it by name, replacing any macro
+    /// with the same name.
+    pub fn stop_recording(&mut self, name: &str) -> Result<(), Error> {
+        let keys = self.recording.take().ok_or(Error::NotRecording)?;
+        self.macros.insert(name.to_string(), keys);
+        Ok(())
+    }
+
+    /// Returns the names of the saved macros.
+    pub fn macros(&self) -> impl Iterator<Item = &str> {
+        self.macros.keys().map(String::as_str)
+    }
+
+    /// Returns the keys of a saved macro if it exists.
+    pub fn macro_keys(&self, name: &str) -> Option<&[Key]> {
+        self.macros.get(name).map(Vec::as_slice)
+    }
+
+    /// Plays a saved macro.
+    ///
+    /// The macro's keys are applied to the current output, discarding any
+    /// pending calculation.
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::Calculator;
+    ///
+    /// let mut calc = Calculator::new();
+    /// calc.start_recording();
+    /// for key in calc.parse_keys("*1.2+15=").unwrap() {
+    ///     calc.press(&key);
+    /// }
+    /// calc.stop_recording("markup").unwrap();
+    ///
+    /// calc.replay(&calc.parse_keys("{AC}100").unwrap());
+    /// calc.play_macro("markup").unwrap();
+    ///
+    /// assert_eq!(calc.display().to_string(), "135");
+    /// ```
+    pub fn play_macro(&mut self, name: &str) -> Result<(), Error> {
+        let keys = self
+            .macros
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::UnknownMacro(name.to_string()))?;
+
+        // Start a new calculation from the output.
+        self.engine.restart();
+
+        for key in keys.iter() {
+            self.press(key);
+        }
+        Ok(())
     }
 
-    /// Clears the buffer.
+    /// Getter function for the display width property.
+    pub fn display_width(&self) -> u8 {
+        self.engine.display_width()
+    }
+
+    /// Sets the number of significant figures that fit on the display.
+    ///
+    /// This limits the number of digits that can be entered, and the number
+    /// of digits output. For example, the iOS calculator displays 9 digits in
+    /// portrait. The width is clamped between
+    /// [`MIN_DISPLAY_WIDTH`](Calculator::MIN_DISPLAY_WIDTH) and
+    /// [`MAX_DISPLAY_WIDTH`](Calculator::MAX_DISPLAY_WIDTH).
+    pub fn set_display_width(&mut self, display_width: u8) {
+        self.engine.set_display_width(display_width);
+    }
+
+    /// Clears the displayed number, keeping the rest of the calculation.
     ///
     /// Will perform a deep 'All clear' (AC) operation if the calulator is currently
     /// cleared.
     pub fn clear(&mut self) {
-        if self.cleared {
-            // All clear.
-            *self = Calculator::new();
-        } else {
-            // Clear.
-            self.display_index = 0;
-            self.buffer[0] = Token::Number(Number::from(0.));
-            self.cleared = true;
-        }
+        self.engine.clear();
     }
 
-    /// Returns the [`Number`] output to be displayed.
-    pub fn output(&mut self) -> &mut Number {
-        match self.buffer.get_mut(self.display_index).unwrap() {
-            Token::Number(number) => return number,
-            _ => {
-                panic!("Display index points to a non-number")
-            }
-        }
+    /// Returns the [`Number`] to be displayed.
+    pub fn display(&self) -> &Number {
+        self.engine.display()
     }
 
-    /// Resolves the calculation stored in the buffer.
-    fn calculate(&self) -> Result<Number, &str> {
-        let mut values = self.buffer.clone();
-        let mut index = values.len() - 1;
-
-        // Iterate through values in reverse order to calculate new result.
-        while index >= 2 {
-            if let Some(Token::Number(number_b)) = values.get(index) {
-                if let Some(Token::Operator(operator)) = values.get(index - 1) {
-                    if let Some(Token::Number(number_a)) = values.get(index - 2) {
-                        values[index - 2] = Token::Number(Number::from((operator.function)(
-                            number_a.value(),
-                            number_b.value(),
-                        )))
-                    }
-                }
-            }
-            index -= 2;
-        }
-        if let Some(Token::Number(result)) = values.first() {
-            return Ok(result.to_owned());
-        }
-        Err("")
+    /// Returns the [`Number`] output to be displayed.
+    #[deprecated(note = "Use `display` to read the output.")]
+    pub fn output(&mut self) -> &mut Number {
+        self.engine.display_mut()
     }
 
     /// Returns the operator that is currently active if it exists.
@@ -95,74 +310,74 @@ impl Calculator {
     /// An operator is active if it is in the last place of the buffer
     /// and is therefore not succeeded by a [`Number`].
     pub fn active_operator(&self) -> Option<&Operator> {
-        if let Some(Token::Operator(operator)) = self.buffer.last() {
-            return Some(operator);
-        }
-        return None;
+        self.engine.active_operator()
     }
 
-    /// Returns the last entered operator in the buffer if one exists.
-    fn last_operator(&self) -> Option<Operator> {
-        // Iterate buffer in reverse order.
-        for index in (0..self.buffer.len()).rev() {
-            if let Some(Token::Operator(operator)) = self.buffer.get(index) {
-                return Some(operator.to_owned());
-            }
+    /// Returns the calculation entered so far, using operator glyphs.
+    ///
+    /// The operation remembered for repeated equals is not included.
+    pub fn expression(&self) -> String {
+        let mut expression: Vec<String> = self.engine.buffer[..=self.engine.display_index]
+            .iter()
+            .map(|token| match token {
+                Token::Number(number) => number.to_string(),
+                Token::Operator(operator) => operator.glyph.to_owned(),
+            })
+            .collect();
+
+        if let Some(operator) = self.active_operator() {
+            expression.push(operator.glyph.to_owned());
         }
-        return None;
+        expression.join(" ")
     }
 
-    /// Performs the 'equals' operation.
-    ///
-    /// This will resolve the calculation stored in the buffer.
-    pub fn submit_equals(&mut self) {
-        self.editing = false;
+    /// Returns the calculation entered so far as it would be read aloud
+    /// in English.
+    pub fn spoken_expression(&self) -> String {
+        let mut expression: Vec<String> = self.engine.buffer[..=self.engine.display_index]
+            .iter()
+            .map(|token| match token {
+                Token::Number(number) => number.spoken(),
+                Token::Operator(operator) => speak_operator(operator),
+            })
+            .collect();
 
-        // Nothing to calculate.
-        if self.buffer.len() <= 2 {
-            return;
+        if let Some(operator) = self.active_operator() {
+            expression.push(speak_operator(operator));
         }
+        expression.join(" ")
+    }
 
-        if let Ok(result) = self.calculate() {
-            // Update result.
-            self.buffer[0] = Token::Number(result);
-            // Display result.
-            self.display_index = 0;
+    /// Checks if the last operation is remembered for repeated equals.
+    pub fn memory(&self) -> bool {
+        self.engine.memory()
+    }
 
-            if self.buffer.len() > 3 {
-                self.buffer.drain(1..3);
-            }
+    /// Returns a [`ViewModel`] describing everything there is to display.
+    pub fn view(&self) -> ViewModel {
+        let display = self.display();
+        ViewModel {
+            display: display.to_string(),
+            expression: self.expression(),
+            spoken: self.spoken_expression(),
+            active_operator: self.active_operator().map(|operator| operator.id),
+            all_clear: self.cleared(),
+            memory: self.memory(),
+            error: display.is_error(),
+            angle_mode: self.angle_mode(),
         }
     }
 
+    /// Performs the 'equals' operation.
+    ///
+    /// This will resolve the calculation stored in the buffer.
+    pub fn submit_equals(&mut self) {
+        self.engine.submit_equals();
+    }
+
     /// Submit an [`Operator`].
     pub fn submit_operator(&mut self, operator: Operator) {
-        self.editing = false;
-
-        match self.buffer.last_mut() {
-            Some(Token::Operator(active_operator)) => {
-                // Replace the existing active operator with the new operator.
-                if active_operator.id != operator.id {
-                    *active_operator = operator;
-                }
-            }
-            Some(Token::Number(..)) => {
-                if self.display_index > 0 {
-                    // If there is already an operator in the buffer.
-                    if let Some(prev_operator) = self.last_operator() {
-                        // If the last is after in the order of operations (BIDMAS).
-                        if prev_operator.after(&operator) {
-                            self.buffer.push(Token::Operator(operator));
-                            return;
-                        }
-                    }
-                    self.submit_equals();
-                }
-                self.buffer.drain(1..self.buffer.len());
-                self.buffer.push(Token::Operator(operator));
-            }
-            None => panic!("Buffer is empty."),
-        }
+        self.engine.submit_operator(operator);
     }
 
     /// Submit a number.
@@ -170,63 +385,85 @@ impl Calculator {
     /// If editing, this will append the number onto the number currently
     /// being edited.
     pub fn submit_number(&mut self, number: u8) {
-        match self.buffer.last() {
-            Some(Token::Number(..)) => {
-                if self.editing {
-                    self.output().append(number);
-                } else {
-                    self.buffer[0] = Token::Number(Number::from(number));
-                }
-            }
-            Some(Token::Operator(..)) => {
-                self.buffer.push(Token::Number(Number::from(number)));
-                self.display_index += 2;
-            }
-            None => panic!("Buffer is empty."),
-        };
-        self.editing = true;
-        self.cleared = false;
+        self.engine.submit_number(number);
     }
 
     /// Performs the 'decimalise' operation.
     pub fn submit_decimal(&mut self) {
-        if !self.editing {
-            // Entering a decimal is handled as equivalent to submitting
-            // a zero when not editing.
-            self.submit_number(0);
-        }
-        self.output().decimalise();
+        self.engine.submit_decimal();
+    }
+
+    /// Performs the 'imaginary' operation.
+    ///
+    /// Multiplies the number being edited by the imaginary unit, or enters
+    /// the imaginary unit if not editing. Has no effect in real mode.
+    pub fn submit_imaginary(&mut self) {
+        self.engine.submit_imaginary();
+    }
+
+    /// Performs the 'π' operation.
+    pub fn submit_pi(&mut self) {
+        self.engine.submit_pi();
+    }
+
+    /// Performs the 'e' operation.
+    pub fn submit_e(&mut self) {
+        self.engine.submit_e();
+    }
+
+    /// Performs the 'Rand' operation.
+    ///
+    /// Enters a random number between zero and one.
+    pub fn submit_random(&mut self) {
+        self.engine.submit_random();
+    }
+
+    /// Performs the 'EE' operation.
+    ///
+    /// Starts entering the exponent of the displayed number, so that a
+    /// constant or result is scaled by it. Entering an exponent is handled as
+    /// equivalent to submitting a zero when awaiting an operand, or when the
+    /// displayed number is complex.
+    pub fn submit_exponent(&mut self) {
+        self.engine.submit_exponent();
     }
 
     /// Performs the 'negative' operation.
+    ///
+    /// Negates the exponent if it is being entered.
     pub fn submit_negative(&mut self) {
-        let output = self.output();
-        output.set_value(output.value() * -1.);
-        self.editing = true;
+        self.engine.submit_negative();
     }
 
     /// Performs the 'percentage' operation.
+    ///
+    /// When adding or subtracting, the output is converted to a percentage of
+    /// the preceding calculation. For example, `50 + 10 %` becomes `50 + 5`.
     pub fn submit_percentage(&mut self) {
-        if self.display_index > 0 {
-            let _ = self.calculate();
-        }
-        let output = self.output();
-        output.set_value(output.value() / 100.);
+        self.engine.submit_percentage();
+    }
+
+    /// Applies a [`Function`] to the output.
+    ///
+    /// In complex mode the function's complex form is used.
+    pub fn submit_function(&mut self, function: Function) {
+        self.engine.submit_function(&function);
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{complex::Complex, operator::Associativity};
+    use core::f64::consts;
+    use proptest::{prelude::*, test_runner::TestCaseError};
 
     /// Calculator used for testing.
     fn test_calculator() -> Calculator {
-        Calculator {
-            display_index: 0,
-            cleared: true,
-            editing: false,
-            buffer: vec![],
-        }
+        let mut calc = Calculator::new();
+        calc.set_angle_mode(AngleMode::Degrees);
+        calc.engine.buffer.clear();
+        calc
     }
 
     /// The example used across documentation.
@@ -241,12 +478,12 @@ mod test {
         calc.submit_equals();
 
         assert_eq!(
-            calc.output().value(),
+            calc.display().value(),
             3.2,
         ); 
 
         assert_eq!(
-            calc.output().to_string(),
+            calc.display().to_string(),
             "3.2",
         ); 
     }
@@ -255,10 +492,10 @@ mod test {
     fn last_operator_none() {
         let number_1 = Number::from(1.);
         let mut calc = test_calculator();
-        calc.buffer = vec![Token::Number(number_1.clone())];
+        calc.engine.buffer = vec![Token::Number(number_1.clone())];
 
         assert_eq!(
-            calc.last_operator(),
+            calc.engine.last_operator(),
             None,
             "Should return none when no operator in the buffer."
         );
@@ -269,7 +506,7 @@ mod test {
         let calc = test_calculator();
 
         assert_eq!(
-            calc.last_operator(),
+            calc.engine.last_operator(),
             None,
             "Should return none when buffer is empty."
         );
@@ -279,11 +516,11 @@ mod test {
     fn submit_percentage() {
         let number_1 = Number::from(1.);
         let mut calc = test_calculator();
-        calc.buffer = vec![Token::Number(number_1.clone())];
+        calc.engine.buffer = vec![Token::Number(number_1.clone())];
         calc.submit_percentage();
 
         assert_eq!(
-            calc.output(),
+            calc.display(),
             &Number::from(0.01),
             "Should convert output to a percentage."
         );
@@ -293,13 +530,876 @@ mod test {
     fn submit_percentage_negative() {
         let number_1 = Number::from(-1.);
         let mut calc = test_calculator();
-        calc.buffer = vec![Token::Number(number_1.clone())];
+        calc.engine.buffer = vec![Token::Number(number_1.clone())];
         calc.submit_percentage();
 
         assert_eq!(
-            calc.output(),
+            calc.display(),
             &Number::from(-0.01),
             "Should convert output to a percentage."
         );
     }
+
+    #[test]
+    fn square_root_negative_real() {
+        let mut calc = Calculator::new();
+        calc.submit_number(4);
+        calc.submit_negative();
+        calc.submit_function(Function::square_root());
+
+        assert!(
+            calc.display().value().is_nan(),
+            "Should have no result in real mode."
+        );
+    }
+
+    #[test]
+    fn square_root_negative_complex() {
+        let mut calc = Calculator::new();
+        calc.set_complex_mode(true);
+        calc.submit_number(4);
+        calc.submit_negative();
+        calc.submit_function(Function::square_root());
+
+        assert_eq!(calc.display().complex(), Complex::new(0., 2.));
+        assert_eq!(calc.display().to_string(), "2i");
+    }
+
+    #[test]
+    fn complex_multiply() {
+        let mut calc = Calculator::new();
+        calc.set_complex_mode(true);
+        calc.submit_number(2);
+        calc.submit_imaginary();
+        calc.submit_operator(Operator::multiply());
+        calc.submit_imaginary();
+        calc.submit_equals();
+
+        assert_eq!(calc.display().complex(), Complex::new(-2., 0.));
+    }
+
+    #[test]
+    fn imaginary_real_mode() {
+        let mut calc = Calculator::new();
+        calc.submit_number(2);
+        calc.submit_imaginary();
+
+        assert!(
+            calc.display().is_real(),
+            "Should have no effect in real mode."
+        );
+    }
+
+    #[test]
+    fn complex_divide_by_zero() {
+        let mut calc = Calculator::new();
+        calc.set_complex_mode(true);
+        calc.submit_number(1);
+        calc.submit_operator(Operator::divide());
+        calc.submit_number(0);
+        calc.submit_equals();
+
+        assert!(calc.display().is_error());
+        assert_eq!(calc.display().to_string(), "Error");
+    }
+
+    #[test]
+    fn toggle_polar() {
+        let mut calc = Calculator::new();
+        calc.set_complex_mode(true);
+        calc.submit_number(1);
+        calc.submit_operator(Operator::add());
+        calc.submit_imaginary();
+        calc.submit_equals();
+        assert_eq!(calc.display().to_string(), "1+i");
+
+        calc.toggle_polar();
+        assert_eq!(calc.display().to_string(), "1.4142∠45°");
+
+        calc.set_angle_mode(AngleMode::Radians);
+        assert_eq!(calc.display().to_string(), "1.4142∠0.785");
+    }
+
+    #[test]
+    fn all_clear_keeps_settings() {
+        let mut calc = Calculator::new();
+        calc.set_complex_mode(true);
+        calc.submit_number(1);
+        calc.clear();
+        calc.clear();
+
+        assert!(calc.complex_mode());
+        assert!(calc.cleared());
+        assert_eq!(calc.display(), &Number::from(0.));
+    }
+
+    #[test]
+    fn clear_entry() {
+        let mut calc = Calculator::new();
+        calc.submit_number(1);
+        calc.submit_number(2);
+        calc.submit_operator(Operator::add());
+        calc.submit_number(3);
+        calc.clear();
+        assert_eq!(calc.display().to_string(), "0");
+
+        calc.submit_number(4);
+        calc.submit_equals();
+        assert_eq!(
+            calc.display().to_string(),
+            "16",
+            "Should only clear the number being entered."
+        );
+    }
+
+    #[test]
+    fn display_mode() {
+        let mut calc = Calculator::new();
+        calc.submit_number(2);
+        calc.submit_operator(Operator::divide());
+        calc.submit_number(3);
+        calc.submit_equals();
+
+        calc.set_display_mode(DisplayMode::Fix(2));
+        assert_eq!(calc.display().to_string(), "0.67");
+
+        calc.submit_operator(Operator::multiply());
+        calc.submit_number(3);
+        assert_eq!(
+            calc.display().to_string(),
+            "3",
+            "Should display numbers as entered while editing."
+        );
+
+        calc.submit_equals();
+        assert_eq!(calc.display().to_string(), "2.00");
+    }
+
+    #[test]
+    fn display_mode_editing() {
+        let mut calc = Calculator::new();
+        calc.set_display_mode(DisplayMode::Fix(2));
+        calc.submit_number(1);
+        assert_eq!(calc.display().to_string(), "1");
+        calc.submit_decimal();
+        assert_eq!(calc.display().to_string(), "1.");
+        calc.submit_operator(Operator::add());
+        assert_eq!(
+            calc.display().to_string(),
+            "1.00",
+            "Should use the display mode once the number is entered."
+        );
+
+        let mut calc = Calculator::new();
+        calc.set_display_mode(DisplayMode::Eng(3));
+        calc.submit_number(0);
+        calc.submit_decimal();
+        assert_eq!(calc.display().to_string(), "0.");
+        for expected in ["0.0", "0.00", "0.000"] {
+            calc.submit_number(0);
+            assert_eq!(calc.display().to_string(), expected);
+        }
+        calc.submit_number(1);
+        assert_eq!(calc.display().to_string(), "0.0001");
+        calc.submit_equals();
+        assert_eq!(calc.display().to_string(), "100.000e-6");
+
+        let mut calc = Calculator::new();
+        calc.set_display_mode(DisplayMode::Sci(1));
+        calc.submit_number(9);
+        assert_eq!(calc.display().to_string(), "9");
+        calc.submit_exponent();
+        assert_eq!(calc.display().to_string(), "9e0");
+        calc.submit_equals();
+        assert_eq!(calc.display().to_string(), "9.0e0");
+    }
+
+    #[test]
+    fn display_width() {
+        let mut calc = Calculator::new();
+        calc.set_display_width(15);
+        for _ in 0..12 {
+            calc.submit_number(1);
+        }
+        assert_eq!(calc.display().to_string(), "111,111,111,111");
+
+        calc.set_display_width(9);
+        assert_eq!(
+            calc.display().to_string(),
+            "1.11111e11",
+            "Should keep the value when the width is reduced."
+        );
+    }
+
+    #[test]
+    fn display_width_results() {
+        let mut calc = Calculator::new();
+        calc.set_display_width(15);
+        calc.submit_number(1);
+        calc.submit_operator(Operator::divide());
+        calc.submit_number(3);
+        calc.submit_equals();
+
+        assert_eq!(calc.display().to_string(), "0.33333333333333");
+    }
+
+    #[test]
+    fn display_width_bounds() {
+        let mut calc = Calculator::new();
+        calc.set_display_width(16);
+        assert_eq!(calc.display_width(), Calculator::MAX_DISPLAY_WIDTH);
+        for _ in 0..16 {
+            calc.submit_number(9);
+        }
+        assert_eq!(
+            calc.display().to_string(),
+            "999,999,999,999,999",
+            "Should display every digit entered."
+        );
+        calc.submit_operator(Operator::add());
+        calc.submit_number(1);
+        calc.submit_equals();
+        assert_eq!(calc.display().to_string(), "1e15");
+
+        calc.set_display_width(0);
+        assert_eq!(calc.display_width(), Calculator::MIN_DISPLAY_WIDTH);
+        calc.submit_decimal();
+        calc.submit_number(5);
+        assert_eq!(calc.display().to_string(), "0.5");
+        calc.submit_number(5);
+        assert_eq!(
+            calc.display().to_string(),
+            "0.5",
+            "Should limit entry to two digits."
+        );
+    }
+
+    #[test]
+    fn submit_exponent() {
+        let mut calc = Calculator::new();
+        calc.submit_number(2);
+        calc.submit_exponent();
+        calc.submit_number(3);
+        calc.submit_negative();
+        assert_eq!(calc.display().to_string(), "2e-3");
+
+        calc.submit_operator(Operator::multiply());
+        calc.submit_number(4);
+        calc.submit_equals();
+        assert_eq!(calc.display().value(), 0.008);
+    }
+
+    #[test]
+    fn submit_exponent_not_editing() {
+        let mut calc = Calculator::new();
+        calc.submit_number(2);
+        calc.submit_operator(Operator::add());
+        calc.submit_exponent();
+        assert_eq!(calc.display().to_string(), "0e0");
+    }
+
+    #[test]
+    fn submit_exponent_after_constant() {
+        let mut calc = Calculator::new();
+        calc.submit_pi();
+        calc.submit_exponent();
+        calc.submit_number(5);
+        assert_eq!(calc.display().value(), consts::PI * 1e5);
+        assert_eq!(calc.display().to_string(), "3.1415926e5");
+
+        calc.submit_operator(Operator::add());
+        calc.submit_number(1);
+        calc.submit_equals();
+        assert_eq!(calc.display().value(), consts::PI * 1e5 + 1.);
+    }
+
+    #[test]
+    fn submit_exponent_after_equals() {
+        let mut calc = Calculator::new();
+        calculate_sequence(&mut calc, &[2, 3], &[Operator::multiply()]);
+        calc.submit_exponent();
+        calc.submit_number(2);
+        calc.submit_negative();
+        assert_eq!(calc.display().to_string(), "6e-2");
+        assert_eq!(calc.display().value(), 0.06);
+
+        calc.submit_equals();
+        assert_eq!(
+            calc.display().value(),
+            0.18,
+            "Should repeat the last operation on the scaled result."
+        );
+    }
+
+    #[test]
+    fn negate_after_exponent() {
+        let mut calc = Calculator::new();
+        calc.submit_number(2);
+        calc.submit_exponent();
+        calc.submit_number(3);
+        calc.submit_equals();
+        calc.submit_negative();
+        assert_eq!(
+            calc.display().value(),
+            -2000.,
+            "Should negate the number when no longer editing."
+        );
+    }
+
+    #[test]
+    fn submit_pi() {
+        let mut calc = Calculator::new();
+        calc.submit_number(2);
+        calc.submit_operator(Operator::multiply());
+        calc.submit_pi();
+        assert_eq!(calc.display().to_string(), "3.14159265");
+
+        calc.submit_equals();
+        assert_eq!(calc.display().value(), 2. * consts::PI);
+    }
+
+    #[test]
+    fn submit_e_ends_editing() {
+        let mut calc = Calculator::new();
+        calc.submit_number(1);
+        calc.submit_e();
+        assert_eq!(calc.display().value(), consts::E);
+
+        calc.submit_number(5);
+        assert_eq!(
+            calc.display().value(),
+            5.,
+            "Should replace the constant rather than append to it."
+        );
+    }
+
+    #[test]
+    fn submit_random_seeded() {
+        let mut calc_a = Calculator::new();
+        let mut calc_b = Calculator::new();
+        calc_a.seed_random(1234);
+        calc_b.seed_random(1234);
+        calc_a.submit_random();
+        calc_b.submit_random();
+
+        assert_eq!(calc_a.display(), calc_b.display());
+        assert!((0. ..1.).contains(&calc_a.display().value()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn submit_random_unseeded() {
+        let mut calc_a = Calculator::new();
+        let mut calc_b = Calculator::new();
+        calc_a.submit_random();
+        calc_b.submit_random();
+
+        assert_ne!(
+            calc_a.display(),
+            calc_b.display(),
+            "Should seed each calculator differently."
+        );
+    }
+
+    /// Submits a sequence of numbers and operators, then equals.
+    fn calculate_sequence(calc: &mut Calculator, numbers: &[u8], operators: &[Operator]) {
+        calc.submit_number(numbers[0]);
+        for (number, operator) in numbers[1..].iter().zip(operators) {
+            calc.submit_operator(operator.clone());
+            calc.submit_number(*number);
+        }
+        calc.submit_equals();
+    }
+
+    #[test]
+    fn power() {
+        let mut calc = Calculator::new();
+        calculate_sequence(&mut calc, &[2, 8], &[Operator::power()]);
+        assert_eq!(calc.display().value(), 256.);
+    }
+
+    #[test]
+    fn power_right_associative() {
+        let mut calc = Calculator::new();
+        calculate_sequence(
+            &mut calc,
+            &[2, 3, 2],
+            &[Operator::power(), Operator::power()],
+        );
+        assert_eq!(calc.display().value(), 512., "Should calculate 2^(3^2).");
+    }
+
+    #[test]
+    fn power_before_multiply() {
+        let mut calc = Calculator::new();
+        calculate_sequence(
+            &mut calc,
+            &[1, 2, 3, 4],
+            &[Operator::add(), Operator::power(), Operator::multiply()],
+        );
+        assert_eq!(
+            calc.display().value(),
+            33.,
+            "Should calculate 1 + (2^3 × 4)."
+        );
+    }
+
+    #[test]
+    fn subtract_left_associative() {
+        let mut calc = Calculator::new();
+        calculate_sequence(
+            &mut calc,
+            &[9, 2, 3, 1],
+            &[
+                Operator::subtract(),
+                Operator::multiply(),
+                Operator::subtract(),
+            ],
+        );
+        assert_eq!(
+            calc.display().value(),
+            2.,
+            "Should calculate (9 - 2 × 3) - 1."
+        );
+    }
+
+    #[test]
+    fn resolves_tail_on_operator() {
+        let mut calc = Calculator::new();
+        calc.submit_number(1);
+        calc.submit_operator(Operator::add());
+        calc.submit_number(2);
+        calc.submit_operator(Operator::multiply());
+        calc.submit_number(3);
+        calc.submit_operator(Operator::multiply());
+
+        assert_eq!(
+            calc.display().value(),
+            6.,
+            "Should only resolve the multiplication."
+        );
+    }
+
+    #[test]
+    fn replace_operator_resolves() {
+        let mut calc = Calculator::new();
+        calc.submit_number(5);
+        calc.submit_operator(Operator::subtract());
+        calc.submit_number(2);
+        calc.submit_operator(Operator::multiply());
+        calc.submit_operator(Operator::subtract());
+        calc.submit_number(1);
+        calc.submit_equals();
+
+        assert_eq!(calc.display().value(), 2., "Should calculate (5 - 2) - 1.");
+    }
+
+    #[test]
+    fn complex_power() {
+        let mut calc = Calculator::new();
+        calc.set_complex_mode(true);
+        calc.submit_number(4);
+        calc.submit_negative();
+        calc.submit_operator(Operator::root());
+        calc.submit_number(2);
+        calc.submit_equals();
+
+        assert_eq!(calc.display().to_string(), "2i");
+    }
+
+    #[test]
+    fn submit_percentage_of_sum() {
+        let mut calc = Calculator::new();
+        calc.submit_number(5);
+        calc.submit_number(0);
+        calc.submit_operator(Operator::add());
+        calc.submit_number(1);
+        calc.submit_number(0);
+        calc.submit_percentage();
+        assert_eq!(calc.display().value(), 5., "Should be a percentage of 50.");
+
+        calc.submit_equals();
+        assert_eq!(calc.display().value(), 55.);
+    }
+
+    #[test]
+    fn submit_percentage_of_product() {
+        let mut calc = Calculator::new();
+        calc.submit_number(5);
+        calc.submit_number(0);
+        calc.submit_operator(Operator::multiply());
+        calc.submit_number(1);
+        calc.submit_number(0);
+        calc.submit_percentage();
+        calc.submit_equals();
+
+        assert_eq!(calc.display().value(), 5.);
+    }
+
+    #[test]
+    fn calculate_long_buffer() {
+        let mut calc = test_calculator();
+        calc.engine.buffer = vec![
+            Token::Number(Number::from(1.)),
+            Token::Operator(Operator::add()),
+            Token::Number(Number::from(2.)),
+            Token::Operator(Operator::multiply()),
+            Token::Number(Number::from(3.)),
+            Token::Operator(Operator::subtract()),
+            Token::Number(Number::from(4.)),
+            Token::Operator(Operator::power()),
+            Token::Number(Number::from(2.)),
+        ];
+
+        assert_eq!(calc.engine.calculate(), Ok(Number::from(-9.)));
+    }
+
+    #[test]
+    fn calculate_malformed() {
+        let mut calc = test_calculator();
+        calc.engine.buffer = vec![
+            Token::Number(Number::from(1.)),
+            Token::Operator(Operator::add()),
+            Token::Operator(Operator::add()),
+        ];
+
+        assert_eq!(calc.engine.calculate(), Err(Error::UnexpectedToken(2)));
+
+        calc.engine.buffer.push(Token::Number(Number::from(1.)));
+        calc.engine.display_index = 3;
+        calc.submit_equals();
+        assert!(
+            calc.display().is_error(),
+            "Should show an error when the calculation fails."
+        );
+        assert_eq!(calc.display().to_string(), "Error");
+
+        calc.submit_number(2);
+        assert_eq!(
+            calc.display().to_string(),
+            "2",
+            "Should start a new number."
+        );
+    }
+
+    #[test]
+    fn memory_keeps_last_operation() {
+        let mut calc = Calculator::new();
+        calculate_sequence(
+            &mut calc,
+            &[1, 2, 3],
+            &[Operator::add(), Operator::multiply()],
+        );
+        assert_eq!(calc.display().value(), 7.);
+
+        calc.submit_equals();
+        assert_eq!(
+            calc.display().value(),
+            21.,
+            "Should repeat the multiplication."
+        );
+    }
+
+    #[test]
+    fn registered_operator_order() {
+        let mut calc = Calculator::new();
+        calc.register_operator(Operator::new(
+            'Δ',
+            "Δ%",
+            3,
+            Associativity::Left,
+            |x, y| (y - x) / x * 100.,
+        ))
+        .unwrap();
+        calc.submit_number(1);
+        calc.submit_operator(calc.operator("+").unwrap());
+        calc.submit_number(4);
+        calc.submit_operator(calc.operator("Δ").unwrap());
+        calc.submit_number(6);
+        calc.submit_equals();
+
+        assert_eq!(
+            calc.display().value(),
+            20.,
+            "Should calculate the percentage difference between 5 and 6."
+        );
+    }
+
+    #[test]
+    fn registered_operator_reserved() {
+        let mut calc = Calculator::new();
+        let operator = Operator::new('=', "=", 3, Associativity::Left, |x, y| x + y);
+        assert_eq!(
+            calc.register_operator(operator),
+            Err(Error::ReservedOperator('='))
+        );
+        assert_eq!(calc.key("="), Ok(Key::Equals));
+    }
+
+    #[test]
+    fn registered_operator_percentage() {
+        let mut calc = Calculator::new();
+        calc.register_operator(Operator::new('+', "+", 3, Associativity::Left, |x, y| {
+            x.hypot(y)
+        }))
+        .unwrap();
+        calc.replay(&calc.parse_keys("50+10%").unwrap());
+        assert_eq!(
+            calc.display().value(),
+            0.1,
+            "Should only take a percentage of the calculation for additive operators."
+        );
+
+        let mut calc = Calculator::new();
+        calc.register_operator(
+            Operator::new('+', "+", 3, Associativity::Left, |x, y| x + y).with_additive(true),
+        )
+        .unwrap();
+        calc.replay(&calc.parse_keys("50+10%").unwrap());
+        assert_eq!(calc.display().value(), 5.);
+    }
+
+    #[test]
+    fn unknown_operator() {
+        let calc = Calculator::new();
+        assert_eq!(calc.operator("m"), None);
+    }
+
+    #[test]
+    fn replay() {
+        let mut calc = Calculator::new();
+        let keys = calc.parse_keys("2*3={AC}{±}").unwrap();
+
+        assert_eq!(calc.replay(&keys), vec!["2", "2", "3", "6", "0", "-0"]);
+    }
+
+    #[test]
+    fn replay_registered_operator() {
+        let mut calc = Calculator::new();
+        calc.register_operator(Operator::new('m', "mod", 2, Associativity::Left, |x, y| {
+            x % y
+        }))
+        .unwrap();
+        let keys = calc.parse_keys("17m5=").unwrap();
+
+        assert_eq!(calc.replay(&keys).last().unwrap(), "2");
+    }
+
+    #[test]
+    fn play_macro() {
+        let mut calc = Calculator::new();
+        calc.start_recording();
+        calc.replay(&calc.parse_keys("*2+1=").unwrap());
+        calc.stop_recording("double").unwrap();
+
+        calc.replay(&calc.parse_keys("5").unwrap());
+        calc.play_macro("double").unwrap();
+        assert_eq!(calc.display().value(), 11.);
+
+        calc.play_macro("double").unwrap();
+        assert_eq!(
+            calc.display().value(),
+            23.,
+            "Should apply to the result of the last calculation."
+        );
+    }
+
+    #[test]
+    fn play_macro_pending_calculation() {
+        let mut calc = Calculator::new();
+        calc.start_recording();
+        calc.replay(&calc.parse_keys("*2=").unwrap());
+        calc.stop_recording("double").unwrap();
+
+        calc.replay(&calc.parse_keys("1+4").unwrap());
+        calc.play_macro("double").unwrap();
+        assert_eq!(
+            calc.display().value(),
+            8.,
+            "Should apply to the output only."
+        );
+    }
+
+    #[test]
+    fn play_unknown_macro() {
+        let mut calc = Calculator::new();
+        assert_eq!(
+            calc.play_macro("missing"),
+            Err(Error::UnknownMacro(String::from("missing")))
+        );
+    }
+
+    #[test]
+    fn stop_without_recording() {
+        let mut calc = Calculator::new();
+        assert_eq!(calc.stop_recording("name"), Err(Error::NotRecording));
+    }
+
+    #[test]
+    fn list_macros() {
+        let mut calc = Calculator::new();
+        for name in ["b", "a"] {
+            calc.start_recording();
+            calc.press(&Key::Equals);
+            calc.stop_recording(name).unwrap();
+        }
+
+        assert_eq!(calc.macros().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(calc.macro_keys("a"), Some(&[Key::Equals][..]));
+    }
+
+    #[test]
+    fn expression() {
+        let mut calc = Calculator::new();
+        calc.submit_number(1);
+        calc.submit_operator(Operator::add());
+        calc.submit_number(2);
+        calc.submit_operator(Operator::multiply());
+        assert_eq!(calc.expression(), "1 + 2 ×");
+
+        calc.submit_number(3);
+        assert_eq!(calc.expression(), "1 + 2 × 3");
+    }
+
+    #[test]
+    fn view() {
+        let mut calc = Calculator::new();
+        calc.submit_number(2);
+        calc.submit_operator(Operator::multiply());
+
+        let view = calc.view();
+        assert_eq!(view.display, "2");
+        assert_eq!(view.active_operator, Some('*'));
+        assert!(!view.all_clear);
+        assert!(!view.memory);
+
+        calc.submit_number(2);
+        calc.submit_equals();
+        let view = calc.view();
+        assert_eq!(view.expression, "4");
+        assert!(view.memory, "Should remember the last operation.");
+        assert_eq!(view.angle_mode, AngleMode::Degrees);
+    }
+
+    #[test]
+    fn view_error() {
+        let mut calc = Calculator::new();
+        calculate_sequence(&mut calc, &[1, 0], &[Operator::divide()]);
+        assert!(
+            calc.view().error,
+            "Should show an error after dividing by zero."
+        );
+    }
+
+    #[test]
+    fn enter_after_error() {
+        let mut calc = Calculator::new();
+        calculate_sequence(&mut calc, &[1, 0], &[Operator::divide()]);
+        calc.submit_negative();
+        calc.submit_number(5);
+        assert_eq!(
+            calc.display().to_string(),
+            "5",
+            "Should start a new number when entering digits after an error."
+        );
+
+        calc.submit_exponent();
+        assert_eq!(calc.display().to_string(), "5e0");
+    }
+
+    #[test]
+    fn display() {
+        let mut calc = Calculator::new();
+        calc.submit_number(7);
+
+        let calc = &calc;
+        assert_eq!(calc.display().to_string(), "7");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn output_edits_display() {
+        let mut calc = Calculator::new();
+        calc.output().append(5);
+        assert_eq!(calc.display().value(), 5.);
+    }
+
+    #[test]
+    fn spoken_expression() {
+        let mut calc = Calculator::new();
+        calculate_sequence(&mut calc, &[1, 2], &[Operator::add()]);
+        calc.submit_operator(Operator::divide());
+        assert_eq!(calc.spoken_expression(), "three divided by");
+    }
+
+    /// Checks the invariants that the calculator relies on.
+    fn check_invariants(calc: &Calculator) -> Result<(), TestCaseError> {
+        for (index, token) in calc.engine.buffer.iter().enumerate() {
+            prop_assert_eq!(
+                matches!(token, Token::Number(..)),
+                index % 2 == 0,
+                "Should alternate numbers and operators, found {:?}.",
+                calc.engine.buffer
+            );
+        }
+        prop_assert!(
+            matches!(
+                calc.engine.buffer.get(calc.engine.display_index),
+                Some(Token::Number(..))
+            ),
+            "Should display a number, found index {} of {:?}.",
+            calc.engine.display_index,
+            calc.engine.buffer
+        );
+        prop_assert!(
+            calc.engine.display_index == 0
+                || calc.engine.display_index == (calc.engine.buffer.len() - 1) & !1,
+            "Should display the last number or the last result, found index {} of {:?}.",
+            calc.engine.display_index,
+            calc.engine.buffer
+        );
+
+        let display = calc.display();
+        if display.is_real() && !display.is_error() {
+            let text = display.to_string();
+            let mantissa = text.split('e').next().unwrap();
+            let digits = mantissa.chars().filter(char::is_ascii_digit).count();
+            prop_assert!(
+                digits <= calc.display_width().into(),
+                "Should fit \"{}\" in {} digits.",
+                text,
+                calc.display_width()
+            );
+        }
+        Ok(())
+    }
+
+    proptest! {
+        /// Presses random keys, checking the invariants after each one.
+        ///
+        /// Failing sequences are shrunk to the fewest keys that fail.
+        #[test]
+        fn invariants(
+            keys in prop::collection::vec(
+                prop::sample::select(
+                    Calculator::new()
+                        .parse_keys("0123456789.+-*/^√㏒{²√}{x²}{1/x}=±%{AC}i∠{Rad}{EE}πe{Rand}")
+                        .unwrap(),
+                ),
+                0..64,
+            ),
+            complex: bool,
+            display_width in prop::sample::select(vec![9, 15]),
+        ) {
+            let mut calc = Calculator::new();
+            calc.set_complex_mode(complex);
+            calc.set_display_width(display_width);
+            check_invariants(&calc)?;
+
+            for key in keys.iter() {
+                calc.press(key);
+                check_invariants(&calc)?;
+            }
+        }
+    }
 }
@@ -0,0 +1,237 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Represents a complex number in rectangular form.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Complex {
+    /// The real part.
+    pub re: f64,
+    /// The imaginary part.
+    pub im: f64,
+}
+
+impl Complex {
+    /// The imaginary unit.
+    pub const I: Complex = Complex { re: 0., im: 1. };
+
+    /// Create a complex number from its real and imaginary parts.
+    pub fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    /// Create a complex number from its modulus and argument (in radians).
+    pub fn from_polar(modulus: f64, argument: f64) -> Self {
+        Complex {
+            re: modulus * argument.cos(),
+            im: modulus * argument.sin(),
+        }
+    }
+
+    /// Returns the modulus (absolute value) of the complex number.
+    pub fn modulus(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Returns the argument of the complex number in radians.
+    pub fn argument(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Checks if the complex number has no imaginary part.
+    pub fn is_real(&self) -> bool {
+        self.im == 0.
+    }
+
+    /// Returns the principal square root.
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::Complex;
+    ///
+    /// assert_eq!(Complex::from(-4.).sqrt(), Complex::new(0., 2.));
+    /// ```
+    pub fn sqrt(&self) -> Self {
+        let modulus = self.modulus();
+        let re = ((modulus + self.re) / 2.).sqrt();
+        let im = ((modulus - self.re) / 2.).sqrt();
+
+        Complex {
+            re,
+            im: if self.im.is_sign_negative() { -im } else { im },
+        }
+    }
+
+    /// Returns the exponential function, `e` raised to the power of the number.
+    pub fn exp(&self) -> Self {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Returns the principal natural logarithm.
+    pub fn ln(&self) -> Self {
+        Complex {
+            re: self.modulus().ln(),
+            im: self.argument(),
+        }
+    }
+
+    /// Returns the principal value of the number raised to a complex power.
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::Complex;
+    ///
+    /// let result = Complex::I.powc(Complex::from(2.));
+    /// assert!((result.re + 1.).abs() < 1e-15);
+    /// assert_eq!(result.im, 0.);
+    /// ```
+    pub fn powc(&self, exponent: Complex) -> Self {
+        if *self == Complex::default() {
+            return if exponent == Complex::default() {
+                Complex::from(1.)
+            } else {
+                Complex::default()
+            };
+        }
+        let result = (exponent * self.ln()).exp();
+
+        // Remove rounding errors that are insignificant relative to the result.
+        let tolerance = result.modulus() * 1e-15;
+        let snap = |part: f64| if part.abs() < tolerance { 0. } else { part };
+        Complex {
+            re: snap(result.re),
+            im: snap(result.im),
+        }
+    }
+
+    /// Returns the reciprocal.
+    pub fn recip(&self) -> Self {
+        Complex::from(1.) / *self
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(value: f64) -> Self {
+        Complex { re: value, im: 0. }
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+
+    fn neg(self) -> Self::Output {
+        Complex {
+            re: -self.re,
+            im: -self.im,
+        }
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Complex {
+            re: self.re + rhs.re,
+            im: self.im + rhs.im,
+        }
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Complex {
+            re: self.re - rhs.re,
+            im: self.im - rhs.im,
+        }
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Complex {
+            re: self.re * rhs.re - self.im * rhs.im,
+            im: self.re * rhs.im + self.im * rhs.re,
+        }
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let denominator = rhs.re * rhs.re + rhs.im * rhs.im;
+        Complex {
+            re: (self.re * rhs.re + self.im * rhs.im) / denominator,
+            im: (self.im * rhs.re - self.re * rhs.im) / denominator,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Complex;
+
+    #[test]
+    fn multiply() {
+        let a = Complex::new(1., 2.);
+        let b = Complex::new(3., -1.);
+        assert_eq!(a * b, Complex::new(5., 5.));
+    }
+
+    #[test]
+    fn divide() {
+        let a = Complex::new(5., 5.);
+        let b = Complex::new(3., -1.);
+        assert_eq!(a / b, Complex::new(1., 2.));
+    }
+
+    #[test]
+    fn sqrt_negative() {
+        assert_eq!(
+            Complex::from(-4.).sqrt(),
+            Complex::new(0., 2.),
+            "Square root of a negative number should be imaginary."
+        );
+    }
+
+    #[test]
+    fn sqrt_complex() {
+        assert_eq!(Complex::new(3., 4.).sqrt(), Complex::new(2., 1.));
+    }
+
+    #[test]
+    fn polar() {
+        let number = Complex::new(0., 2.);
+        assert_eq!(number.modulus(), 2.);
+        assert_eq!(number.argument(), std::f64::consts::FRAC_PI_2);
+    }
+
+    #[test]
+    fn powc_real() {
+        let result = Complex::from(2.).powc(Complex::from(3.));
+        assert!((result.re - 8.).abs() < 1e-12);
+        assert!(result.im.abs() < 1e-12);
+    }
+
+    #[test]
+    fn powc_zero() {
+        assert_eq!(
+            Complex::default().powc(Complex::from(2.)),
+            Complex::default()
+        );
+    }
+
+    #[test]
+    fn ln_negative() {
+        assert_eq!(
+            Complex::from(-1.).ln(),
+            Complex::new(0., std::f64::consts::PI),
+            "Natural logarithm of a negative number should be complex."
+        );
+    }
+}
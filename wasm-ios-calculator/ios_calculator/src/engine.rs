@@ -0,0 +1,556 @@
+use crate::{
+    angle::AngleMode,
+    calculator::Calculator,
+    complex::Complex,
+    error::Error,
+    evaluator::evaluate,
+    function::Function,
+    key::Key,
+    number::{ComplexNotation, DisplayMode, Number},
+    random::Random,
+    storage::{Operation, Stack, Storage, StoredNumber},
+};
+use core::f64::consts;
+
+/// A token that can be entered into a calculator's buffer.
+///
+/// Can be one of:
+/// - [`Number`] - A representation of a number.
+/// - [`Operator`](crate::Operator) - A representation of a mathematical
+///   operator.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum Token<S: Storage> {
+    Number(StoredNumber<S>),
+    Operator(S::Operator),
+}
+
+/// The state of a calculator and how it handles keys, shared by
+/// [`Calculator`] and [`FixedCalculator`](crate::FixedCalculator) with their
+/// own [`Storage`].
+#[derive(Debug, Clone)]
+pub(crate) struct Engine<S: Storage> {
+    /// The tokens that can be parsed to calculate the output.
+    pub(crate) buffer: S::Tokens,
+    /// The index into the buffer.
+    pub(crate) display_index: usize,
+    /// Is the calculator's buffer cleared?
+    cleared: bool,
+    /// Is the calculator in an editing state?
+    editing: bool,
+    /// Does the calculator operate on complex values?
+    complex: bool,
+    /// Are complex values displayed in polar notation?
+    polar: bool,
+    /// The unit used for angles.
+    angle_mode: AngleMode,
+    /// The mode used to display real values.
+    display_mode: DisplayMode,
+    /// The number of significant figures that fit on the display.
+    display_width: u8,
+    /// The generator used for random numbers.
+    random: Random,
+}
+
+impl<S: Storage> Engine<S> {
+    /// Create a new calculator.
+    pub(crate) fn new() -> Self {
+        let mut buffer = S::Tokens::default();
+        buffer.push(Token::Number(Number::from_complex(0.0.into())));
+        Engine {
+            buffer,
+            display_index: 0,
+            cleared: true,
+            editing: false,
+            complex: false,
+            polar: false,
+            angle_mode: AngleMode::default(),
+            display_mode: DisplayMode::default(),
+            display_width: 9,
+            random: Random::default(),
+        }
+    }
+
+    /// Getter function for the cleared property.
+    pub(crate) fn cleared(&self) -> bool {
+        self.cleared
+    }
+
+    /// Getter function for the complex mode property.
+    pub(crate) fn complex_mode(&self) -> bool {
+        self.complex
+    }
+
+    /// Sets whether the calculator operates on complex values.
+    pub(crate) fn set_complex_mode(&mut self, complex: bool) {
+        self.complex = complex;
+    }
+
+    /// Getter function for the polar property.
+    pub(crate) fn polar(&self) -> bool {
+        self.polar
+    }
+
+    /// Toggles the display of complex values between rectangular and polar
+    /// notation.
+    pub(crate) fn toggle_polar(&mut self) {
+        self.polar = !self.polar;
+        self.update_format();
+    }
+
+    /// Getter function for the angle mode property.
+    pub(crate) fn angle_mode(&self) -> AngleMode {
+        self.angle_mode
+    }
+
+    /// Sets the unit used for angles.
+    pub(crate) fn set_angle_mode(&mut self, angle_mode: AngleMode) {
+        self.angle_mode = angle_mode;
+        self.update_format();
+    }
+
+    /// Getter function for the display mode property.
+    pub(crate) fn display_mode(&self) -> DisplayMode {
+        self.display_mode
+    }
+
+    /// Sets the mode used to display real values.
+    pub(crate) fn set_display_mode(&mut self, display_mode: DisplayMode) {
+        self.display_mode = display_mode;
+        self.update_format();
+    }
+
+    /// Seeds the generator used for random numbers.
+    pub(crate) fn seed_random(&mut self, seed: u64) {
+        self.random = Random::new(seed);
+    }
+
+    /// Getter function for the display width property.
+    pub(crate) fn display_width(&self) -> u8 {
+        self.display_width
+    }
+
+    /// Sets the number of significant figures that fit on the display,
+    /// clamped between [`Calculator::MIN_DISPLAY_WIDTH`] and
+    /// [`Calculator::MAX_DISPLAY_WIDTH`].
+    pub(crate) fn set_display_width(&mut self, display_width: u8) {
+        self.display_width =
+            display_width.clamp(Calculator::MIN_DISPLAY_WIDTH, Calculator::MAX_DISPLAY_WIDTH);
+        self.update_format();
+    }
+
+    /// Presses a key.
+    pub(crate) fn press(&mut self, key: &Key) {
+        match key {
+            Key::Number(number) => self.submit_number(*number),
+            Key::Decimal => self.submit_decimal(),
+            Key::Operator(operator) => self.submit_operator(S::Operator::from_operator(operator)),
+            Key::Function(function) => self.submit_function(function),
+            Key::Equals => self.submit_equals(),
+            Key::Negative => self.submit_negative(),
+            Key::Percentage => self.submit_percentage(),
+            Key::Clear => self.clear(),
+            Key::Imaginary => self.submit_imaginary(),
+            Key::Polar => self.toggle_polar(),
+            Key::AngleMode => self.set_angle_mode(self.angle_mode.toggled()),
+            Key::Exponent => self.submit_exponent(),
+            Key::Pi => self.submit_pi(),
+            Key::E => self.submit_e(),
+            Key::Random => self.submit_random(),
+        }
+    }
+
+    /// Starts a new calculation from the displayed number, discarding any
+    /// pending calculation.
+    pub(crate) fn restart(&mut self) {
+        let output = self.display().clone();
+        self.buffer = S::Tokens::default();
+        self.buffer.push(Token::Number(output));
+        self.display_index = 0;
+        self.set_editing(false);
+    }
+
+    /// Returns the notation used to display complex values.
+    fn notation(&self) -> ComplexNotation {
+        if self.polar {
+            ComplexNotation::Polar(self.angle_mode)
+        } else {
+            ComplexNotation::Rectangular
+        }
+    }
+
+    /// Applies the calculator's display settings to every [`Number`] in the
+    /// buffer.
+    fn update_format(&mut self) {
+        let notation = self.notation();
+        for token in self.buffer.iter_mut() {
+            if let Token::Number(number) = token {
+                number.set_notation(notation);
+                number.set_display_mode(self.display_mode);
+                number.set_sf(Some(self.display_width));
+            }
+        }
+    }
+
+    /// Creates a [`Number`] using the calculator's display settings.
+    fn number<T: Into<Complex>>(&self, value: T) -> StoredNumber<S> {
+        let mut number = Number::from_complex(value.into());
+        number.set_notation(self.notation());
+        number.set_display_mode(self.display_mode);
+        number.set_sf(Some(self.display_width));
+        number
+    }
+
+    /// Clears the displayed number, keeping the rest of the calculation.
+    ///
+    /// Will perform a deep 'All clear' (AC) operation if the calulator is currently
+    /// cleared.
+    pub(crate) fn clear(&mut self) {
+        if self.cleared {
+            // All clear, preserving the calculator's settings.
+            self.buffer = S::Tokens::default();
+            self.buffer.push(Token::Number(self.number(0.)));
+            self.display_index = 0;
+            self.set_editing(false);
+        } else {
+            // Clear.
+            self.buffer[self.display_index] = Token::Number(self.number(0.));
+            self.cleared = true;
+        }
+    }
+
+    /// Returns the [`Number`] to be displayed.
+    pub(crate) fn display(&self) -> &StoredNumber<S> {
+        match &self.buffer[self.display_index] {
+            Token::Number(number) => number,
+            _ => {
+                panic!("Display index points to a non-number")
+            }
+        }
+    }
+
+    /// Returns the displayed [`Number`] so that it can be edited.
+    pub(crate) fn display_mut(&mut self) -> &mut StoredNumber<S> {
+        match &mut self.buffer[self.display_index] {
+            Token::Number(number) => number,
+            _ => {
+                panic!("Display index points to a non-number")
+            }
+        }
+    }
+
+    /// Sets whether the displayed number is being edited, so that it is
+    /// formatted as entered.
+    fn set_editing(&mut self, editing: bool) {
+        self.editing = editing;
+        self.display_mut().set_editing(editing);
+    }
+
+    /// Resolves the calculation stored in the buffer.
+    pub(crate) fn calculate(&self) -> Result<StoredNumber<S>, Error> {
+        self.calculate_tokens(&self.buffer)
+    }
+
+    /// Resolves the calculation stored in a slice of the buffer.
+    fn calculate_tokens(&self, tokens: &[Token<S>]) -> Result<StoredNumber<S>, Error> {
+        evaluate(tokens, |operator, number_a, number_b| {
+            self.operate(operator, number_a, number_b)
+        })
+    }
+
+    /// Applies an operator to two numbers.
+    ///
+    /// In complex mode the operator's complex function is used.
+    fn operate(
+        &self,
+        operator: &S::Operator,
+        number_a: &StoredNumber<S>,
+        number_b: &StoredNumber<S>,
+    ) -> StoredNumber<S> {
+        if self.complex {
+            self.number(operator.apply_complex(number_a.complex(), number_b.complex()))
+        } else {
+            self.number(operator.apply(number_a.value(), number_b.value()))
+        }
+    }
+
+    /// Resolves the calculation in the buffer from an index onwards,
+    /// replacing it with its result.
+    fn resolve_from(&mut self, index: usize) -> Result<(), Error> {
+        let result = self.calculate_tokens(&self.buffer[index..])?;
+        self.buffer.truncate(index);
+        self.buffer.push(Token::Number(result));
+        self.display_index = index;
+        Ok(())
+    }
+
+    /// Returns the operator that is currently active if it exists.
+    ///
+    /// An operator is active if it is in the last place of the buffer
+    /// and is therefore not succeeded by a [`Number`].
+    pub(crate) fn active_operator(&self) -> Option<&S::Operator> {
+        if let Some(Token::Operator(operator)) = self.buffer.last() {
+            return Some(operator);
+        }
+        None
+    }
+
+    /// Checks if the last operation is remembered for repeated equals.
+    pub(crate) fn memory(&self) -> bool {
+        self.display_index == 0
+            && self.buffer.len() == 3
+            && matches!(self.buffer.last(), Some(Token::Number(..)))
+    }
+
+    /// Returns the last entered operator in the buffer if one exists.
+    pub(crate) fn last_operator(&self) -> Option<S::Operator> {
+        // Iterate buffer in reverse order.
+        for index in (0..self.buffer.len()).rev() {
+            if let Some(Token::Operator(operator)) = self.buffer.get(index) {
+                return Some(operator.clone());
+            }
+        }
+        None
+    }
+
+    /// Performs the 'equals' operation.
+    ///
+    /// This will resolve the calculation stored in the buffer.
+    pub(crate) fn submit_equals(&mut self) {
+        self.set_editing(false);
+
+        // Nothing to calculate, or the last operand hasn't been entered.
+        if self.buffer.len() <= 2 || self.active_operator().is_some() {
+            return;
+        }
+
+        match self.calculate() {
+            Ok(result) => {
+                // Update result.
+                self.buffer[0] = Token::Number(result);
+                // Display result.
+                self.display_index = 0;
+
+                // Keep the last operation as memory for repeated equals.
+                if self.buffer.len() > 3 {
+                    self.buffer.remove_range(1..self.buffer.len() - 2);
+                }
+            }
+            Err(..) => self.fail(),
+        }
+    }
+
+    /// Replaces the calculation with an error, shown on the display.
+    fn fail(&mut self) {
+        self.buffer = S::Tokens::default();
+        self.buffer.push(Token::Number(self.number(f64::NAN)));
+        self.display_index = 0;
+        self.set_editing(false);
+    }
+
+    /// Submit an operator.
+    ///
+    /// The calculation fails if the buffer has no room for the operator and
+    /// its operand.
+    pub(crate) fn submit_operator(&mut self, operator: S::Operator) {
+        self.set_editing(false);
+
+        match self.buffer.last() {
+            Some(Token::Operator(..)) => {
+                // Replace the existing active operator with the new operator.
+                self.buffer.pop();
+                self.submit_operator(operator);
+            }
+            Some(Token::Number(..)) => {
+                if self.display_index > 0 {
+                    // Find the start of the previous operations that come before
+                    // the new operator in the order of operations (BIDMAS).
+                    let mut index = self.buffer.len() - 1;
+                    while let Some(Token::Operator(prev_operator)) = index
+                        .checked_sub(1)
+                        .and_then(|index| self.buffer.get(index))
+                    {
+                        if !prev_operator.precedes(&operator) {
+                            break;
+                        }
+                        index -= 2;
+                    }
+                    // Resolve them.
+                    if index < self.display_index && self.resolve_from(index).is_err() {
+                        self.fail();
+                    }
+                } else {
+                    // Discard the memory of the last calculation.
+                    self.buffer.truncate(1);
+                }
+
+                if !self.buffer.has_room(2) {
+                    // The calculation is too long to store.
+                    self.fail();
+                    return;
+                }
+                self.buffer.push(Token::Operator(operator));
+            }
+            None => panic!("Buffer is empty."),
+        }
+    }
+
+    /// Submit a number.
+    ///
+    /// If editing, this will append the number onto the number currently
+    /// being edited.
+    pub(crate) fn submit_number(&mut self, number: u8) {
+        match self.buffer.last() {
+            Some(Token::Number(..)) => {
+                if self.editing {
+                    self.display_mut().append(number);
+                } else {
+                    self.buffer[self.display_index] = Token::Number(self.number(f64::from(number)));
+                }
+            }
+            Some(Token::Operator(..)) => {
+                self.buffer
+                    .push(Token::Number(self.number(f64::from(number))));
+                self.display_index += 2;
+            }
+            None => panic!("Buffer is empty."),
+        };
+        self.set_editing(true);
+        self.cleared = false;
+    }
+
+    /// Performs the 'decimalise' operation.
+    pub(crate) fn submit_decimal(&mut self) {
+        if !self.editing {
+            // Entering a decimal is handled as equivalent to submitting
+            // a zero when not editing.
+            self.submit_number(0);
+        }
+        self.display_mut().decimalise();
+    }
+
+    /// Performs the 'imaginary' operation.
+    ///
+    /// Multiplies the number being edited by the imaginary unit, or enters
+    /// the imaginary unit if not editing. Has no effect in real mode.
+    pub(crate) fn submit_imaginary(&mut self) {
+        if !self.complex {
+            return;
+        }
+        if !self.editing {
+            self.submit_number(1);
+        }
+        self.display_mut().imaginarise();
+        self.set_editing(false);
+    }
+
+    /// Replaces the current operand with a constant value.
+    fn submit_constant(&mut self, value: f64) {
+        let number = Token::Number(self.number(value));
+        match self.buffer.last() {
+            Some(Token::Number(..)) => {
+                self.buffer[self.display_index] = number;
+            }
+            Some(Token::Operator(..)) => {
+                self.buffer.push(number);
+                self.display_index += 2;
+            }
+            None => panic!("Buffer is empty."),
+        };
+        self.set_editing(false);
+        self.cleared = false;
+    }
+
+    /// Performs the 'π' operation.
+    pub(crate) fn submit_pi(&mut self) {
+        self.submit_constant(consts::PI);
+    }
+
+    /// Performs the 'e' operation.
+    pub(crate) fn submit_e(&mut self) {
+        self.submit_constant(consts::E);
+    }
+
+    /// Performs the 'Rand' operation.
+    ///
+    /// Enters a random number between zero and one.
+    pub(crate) fn submit_random(&mut self) {
+        let value = self.random.next_f64();
+        self.submit_constant(value);
+    }
+
+    /// Performs the 'EE' operation.
+    ///
+    /// Starts entering the exponent of the displayed number, so that a
+    /// constant or result is scaled by it. Entering an exponent is handled as
+    /// equivalent to submitting a zero when awaiting an operand, or when the
+    /// displayed number is complex.
+    pub(crate) fn submit_exponent(&mut self) {
+        let operand = matches!(self.buffer.last(), Some(Token::Number(..)));
+        let scalable = self.editing || (operand && self.display().is_real());
+        if !scalable {
+            self.submit_number(0);
+        }
+        self.display_mut().start_exponent();
+        self.set_editing(true);
+        self.cleared = false;
+    }
+
+    /// Performs the 'negative' operation.
+    ///
+    /// Negates the exponent if it is being entered.
+    pub(crate) fn submit_negative(&mut self) {
+        let editing = self.editing;
+        let output = self.display_mut();
+        if editing && output.entering_exponent() {
+            output.negate_exponent();
+        } else if output.is_real() {
+            output.set_value(-output.value());
+        } else {
+            output.set_complex(-output.complex());
+        }
+        self.set_editing(true);
+    }
+
+    /// Performs the 'percentage' operation.
+    ///
+    /// When adding or subtracting, the output is converted to a percentage of
+    /// the preceding calculation. For example, `50 + 10 %` becomes `50 + 5`.
+    pub(crate) fn submit_percentage(&mut self) {
+        let mut base = Complex::from(1.);
+
+        if self.display_index > 0 {
+            if let Some(operator) = self.last_operator() {
+                if operator.is_additive() {
+                    let tokens = &self.buffer[..self.display_index - 1];
+                    base = match self.calculate_tokens(tokens) {
+                        Ok(result) => result.complex(),
+                        Err(..) => Complex::from(f64::NAN),
+                    };
+                }
+            }
+        }
+
+        let output = self.display_mut();
+        if output.is_real() && base.is_real() {
+            output.set_value(base.re * output.value() / 100.);
+        } else {
+            output.set_complex(base * output.complex() / 100.0.into());
+        }
+    }
+
+    /// Applies a [`Function`] to the output.
+    ///
+    /// In complex mode the function's complex form is used.
+    pub(crate) fn submit_function(&mut self, function: &Function) {
+        self.set_editing(false);
+        self.cleared = false;
+
+        if self.complex {
+            let output = self.display_mut();
+            output.set_complex((function.complex_function)(output.complex()));
+        } else {
+            let output = self.display_mut();
+            output.set_value((function.function)(output.value()));
+        }
+    }
+}
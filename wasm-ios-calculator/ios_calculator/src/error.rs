@@ -0,0 +1,38 @@
+use alloc::string::String;
+use core::fmt::Display;
+
+/// An error produced by the calculator.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Error {
+    /// The buffer has no tokens to evaluate.
+    EmptyBuffer,
+    /// The buffer ends with an operator that has no right operand.
+    MissingOperand,
+    /// The buffer has a token in an unexpected position, such as two
+    /// consecutive numbers or operators.
+    UnexpectedToken(usize),
+    /// A key identifier that doesn't match any key.
+    UnknownKey(String),
+    /// A macro was stopped without being recorded.
+    NotRecording,
+    /// A macro name that doesn't match any saved macro.
+    UnknownMacro(String),
+    /// An operator identifier that is reserved for another key.
+    ReservedOperator(char),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::EmptyBuffer => write!(f, "The buffer is empty."),
+            Error::MissingOperand => write!(f, "The last operator is missing an operand."),
+            Error::UnexpectedToken(index) => write!(f, "Unexpected token at index {}.", index),
+            Error::UnknownKey(id) => write!(f, "Unknown key \"{}\".", id),
+            Error::NotRecording => write!(f, "No macro is being recorded."),
+            Error::UnknownMacro(name) => write!(f, "Unknown macro \"{}\".", name),
+            Error::ReservedOperator(id) => write!(f, "The \"{}\" key can't be an operator.", id),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
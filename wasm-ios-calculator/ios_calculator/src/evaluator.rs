@@ -0,0 +1,155 @@
+use crate::{
+    engine::Token,
+    error::Error,
+    storage::{Operation, Stack, Storage, StoredNumber},
+};
+
+/// Evaluates a sequence of tokens, respecting the order of operations.
+///
+/// The tokens must alternate between numbers and operators, starting and
+/// ending with a number. Operators are applied using the `operate` function,
+/// which allows the caller to choose how values are combined.
+///
+/// Uses the shunting-yard algorithm, so any mix of operator orders and
+/// associativity is supported. The stacks it uses are those of the tokens'
+/// [`Storage`].
+pub fn evaluate<S: Storage, F>(tokens: &[Token<S>], operate: F) -> Result<StoredNumber<S>, Error>
+where
+    F: Fn(&S::Operator, &StoredNumber<S>, &StoredNumber<S>) -> StoredNumber<S>,
+{
+    let mut numbers: S::Stack<StoredNumber<S>> = Default::default();
+    let mut operators: S::Stack<&S::Operator> = Default::default();
+
+    // Applies the operator to the last two numbers.
+    let apply = |numbers: &mut S::Stack<StoredNumber<S>>, operator: &S::Operator| {
+        let number_b = numbers.pop().unwrap();
+        let number_a = numbers.pop().unwrap();
+        numbers.push(operate(operator, &number_a, &number_b));
+    };
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Number(number) if index % 2 == 0 => {
+                numbers.push(number.clone());
+            }
+            Token::Operator(operator) if index % 2 == 1 => {
+                // Apply previous operators that come before this one in the
+                // order of operations.
+                while let Some(prev_operator) = operators.last() {
+                    if !prev_operator.precedes(operator) {
+                        break;
+                    }
+                    apply(&mut numbers, operators.pop().unwrap());
+                }
+                operators.push(operator);
+            }
+            _ => return Err(Error::UnexpectedToken(index)),
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err(Error::EmptyBuffer);
+    }
+    if let Some(Token::Operator(..)) = tokens.last() {
+        return Err(Error::MissingOperand);
+    }
+
+    while let Some(operator) = operators.pop() {
+        apply(&mut numbers, operator);
+    }
+    Ok(numbers.pop().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::evaluate;
+    use crate::{
+        calculator::Token, error::Error, number::Number, operator::Operator, storage::Heap,
+    };
+
+    /// Applies operators to real values.
+    fn operate(operator: &Operator, number_a: &Number, number_b: &Number) -> Number {
+        Number::from((operator.function)(number_a.value(), number_b.value()))
+    }
+
+    fn number(value: f64) -> Token {
+        Token::Number(Number::from(value))
+    }
+
+    #[test]
+    fn single_number() {
+        let result = evaluate(&[number(4.)], operate);
+        assert_eq!(result, Ok(Number::from(4.)));
+    }
+
+    #[test]
+    fn mixed_orders() {
+        // 1 + 2 × 3 ^ 2 - 4 ÷ 2
+        let tokens = [
+            number(1.),
+            Token::Operator(Operator::add()),
+            number(2.),
+            Token::Operator(Operator::multiply()),
+            number(3.),
+            Token::Operator(Operator::power()),
+            number(2.),
+            Token::Operator(Operator::subtract()),
+            number(4.),
+            Token::Operator(Operator::divide()),
+            number(2.),
+        ];
+        let result = evaluate(&tokens, operate);
+        assert_eq!(result, Ok(Number::from(17.)));
+    }
+
+    #[test]
+    fn left_associative() {
+        // 8 - 4 - 2
+        let tokens = [
+            number(8.),
+            Token::Operator(Operator::subtract()),
+            number(4.),
+            Token::Operator(Operator::subtract()),
+            number(2.),
+        ];
+        let result = evaluate(&tokens, operate);
+        assert_eq!(result, Ok(Number::from(2.)));
+    }
+
+    #[test]
+    fn right_associative() {
+        // 2 ^ 3 ^ 2
+        let tokens = [
+            number(2.),
+            Token::Operator(Operator::power()),
+            number(3.),
+            Token::Operator(Operator::power()),
+            number(2.),
+        ];
+        let result = evaluate(&tokens, operate);
+        assert_eq!(result, Ok(Number::from(512.)));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(evaluate::<Heap, _>(&[], operate), Err(Error::EmptyBuffer));
+    }
+
+    #[test]
+    fn missing_operand() {
+        let tokens = [number(1.), Token::Operator(Operator::add())];
+        assert_eq!(evaluate(&tokens, operate), Err(Error::MissingOperand));
+    }
+
+    #[test]
+    fn consecutive_numbers() {
+        let tokens = [number(1.), number(2.)];
+        assert_eq!(evaluate(&tokens, operate), Err(Error::UnexpectedToken(1)));
+    }
+
+    #[test]
+    fn leading_operator() {
+        let tokens = [Token::Operator(Operator::add()), number(2.)];
+        assert_eq!(evaluate(&tokens, operate), Err(Error::UnexpectedToken(0)));
+    }
+}
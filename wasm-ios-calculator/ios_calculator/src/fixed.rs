@@ -0,0 +1,568 @@
+use crate::{
+    angle::AngleMode,
+    calculator::Calculator,
+    complex::Complex,
+    engine::{Engine, Token},
+    function::Function,
+    key::Key,
+    number::{DisplayMode, Number},
+    operator::{Associativity, Operator},
+    storage::{Operation, Storage, Text},
+};
+use arrayvec::{ArrayString, ArrayVec};
+use core::{
+    fmt::{self, Write},
+    ops::Deref,
+};
+
+/// The number of characters [`InlineText`] stores.
+///
+/// This fits every number that can be entered on the widest display, with a
+/// sign and a decimal point.
+const DIGITS: usize = Calculator::MAX_DISPLAY_WIDTH as usize + 2;
+
+/// The text of a [`FixedNumber`], stored inline.
+///
+/// Writing text that doesn't fit fails.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct InlineText(ArrayString<DIGITS>);
+
+impl Deref for InlineText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Write for InlineText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+impl Text for InlineText {
+    fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
+    fn insert(&mut self, index: usize, c: char) -> fmt::Result {
+        let mut inserted = ArrayString::new();
+        inserted.write_str(&self.0[..index])?;
+        inserted.write_char(c)?;
+        inserted.write_str(&self.0[index..])?;
+        self.0 = inserted;
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> char {
+        self.0.remove(index)
+    }
+}
+
+/// A [`Number`] that stores its digits inline, so that entering and
+/// formatting it doesn't allocate.
+///
+/// Numbers whose shortest string value is too long to store inline can't be
+/// entered onto.
+pub type FixedNumber = Number<InlineText>;
+
+/// An [`Operator`] without its glyph, so that it can be stored inline.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FixedOperator {
+    id: char,
+    function: fn(f64, f64) -> f64,
+    complex_function: Option<fn(Complex, Complex) -> Complex>,
+    order: usize,
+    associativity: Associativity,
+    additive: bool,
+}
+
+impl PartialEq for FixedOperator {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.order == other.order
+    }
+}
+
+impl Operation for FixedOperator {
+    fn from_operator(operator: &Operator) -> Self {
+        FixedOperator {
+            id: operator.id,
+            function: operator.function,
+            complex_function: operator.complex_function,
+            order: operator.order(),
+            associativity: operator.associativity(),
+            additive: operator.is_additive(),
+        }
+    }
+
+    fn id(&self) -> char {
+        self.id
+    }
+
+    fn apply(&self, x: f64, y: f64) -> f64 {
+        (self.function)(x, y)
+    }
+
+    fn apply_complex(&self, x: Complex, y: Complex) -> Complex {
+        match self.complex_function {
+            Some(complex_function) => complex_function(x, y),
+            None if x.is_real() && y.is_real() => Complex::from((self.function)(x.re, y.re)),
+            None => Complex::from(f64::NAN),
+        }
+    }
+
+    fn precedes(&self, next: &Self) -> bool {
+        match self.order.cmp(&next.order) {
+            core::cmp::Ordering::Less => true,
+            core::cmp::Ordering::Equal => next.associativity == Associativity::Left,
+            core::cmp::Ordering::Greater => false,
+        }
+    }
+
+    fn is_additive(&self) -> bool {
+        self.additive
+    }
+}
+
+/// Storage for up to `N` tokens inline.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Inline<const N: usize>;
+
+impl<const N: usize> Storage for Inline<N> {
+    type Text = InlineText;
+    type Operator = FixedOperator;
+    type Tokens = ArrayVec<Token<Self>, N>;
+    type Stack<T> = ArrayVec<T, N>;
+}
+
+/// A [`Calculator`] that stores its buffer inline, holding up to `N`
+/// numbers and operators, so that pressing keys doesn't allocate.
+///
+/// Keys are handled identically to [`Calculator`], except that the
+/// calculation fails with an error if the buffer has no room for an operator
+/// and its operand. The display is at most
+/// [`MAX_DISPLAY_WIDTH`](FixedCalculator::MAX_DISPLAY_WIDTH) wide, and macros
+/// aren't supported.
+///
+/// Example
+/// ```rust
+/// use ios_calculator::{parse_keys, FixedCalculator, OperatorRegistry};
+///
+/// let keys = parse_keys("1.2+2=", &OperatorRegistry::default()).unwrap();
+///
+/// let mut calc: FixedCalculator = FixedCalculator::new();
+/// for key in keys.iter() {
+///     calc.press(key);
+/// }
+///
+/// assert_eq!(calc.display().to_string(), "3.2");
+/// ```
+///
+/// The buffer must hold at least one number.
+/// ```compile_fail
+/// let calc = ios_calculator::FixedCalculator::<0>::new();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedCalculator<const N: usize = 16> {
+    /// The state of the calculator, stored inline.
+    engine: Engine<Inline<N>>,
+}
+
+impl<const N: usize> Default for FixedCalculator<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> FixedCalculator<N> {
+    /// The smallest number of significant figures that fit on the display,
+    /// see [`Calculator::MIN_DISPLAY_WIDTH`].
+    pub const MIN_DISPLAY_WIDTH: u8 = Calculator::MIN_DISPLAY_WIDTH;
+
+    /// The largest number of significant figures that fit on the display,
+    /// see [`Calculator::MAX_DISPLAY_WIDTH`].
+    pub const MAX_DISPLAY_WIDTH: u8 = Calculator::MAX_DISPLAY_WIDTH;
+
+    /// Create a new calculator.
+    pub fn new() -> Self {
+        const { assert!(N >= 1, "The buffer must hold at least one number.") };
+        FixedCalculator {
+            engine: Engine::new(),
+        }
+    }
+
+    /// Getter function for the cleared property.
+    pub fn cleared(&self) -> bool {
+        self.engine.cleared()
+    }
+
+    /// Getter function for the complex mode property.
+    pub fn complex_mode(&self) -> bool {
+        self.engine.complex_mode()
+    }
+
+    /// Sets whether the calculator operates on complex values.
+    pub fn set_complex_mode(&mut self, complex: bool) {
+        self.engine.set_complex_mode(complex);
+    }
+
+    /// Getter function for the polar property.
+    pub fn polar(&self) -> bool {
+        self.engine.polar()
+    }
+
+    /// Toggles the display of complex values between rectangular and polar
+    /// notation.
+    pub fn toggle_polar(&mut self) {
+        self.engine.toggle_polar();
+    }
+
+    /// Getter function for the angle mode property.
+    pub fn angle_mode(&self) -> AngleMode {
+        self.engine.angle_mode()
+    }
+
+    /// Sets the unit used for angles.
+    pub fn set_angle_mode(&mut self, angle_mode: AngleMode) {
+        self.engine.set_angle_mode(angle_mode);
+    }
+
+    /// Getter function for the display mode property.
+    pub fn display_mode(&self) -> DisplayMode {
+        self.engine.display_mode()
+    }
+
+    /// Sets the mode used to display real values.
+    pub fn set_display_mode(&mut self, display_mode: DisplayMode) {
+        self.engine.set_display_mode(display_mode);
+    }
+
+    /// Getter function for the display width property.
+    pub fn display_width(&self) -> u8 {
+        self.engine.display_width()
+    }
+
+    /// Sets the number of significant figures that fit on the display,
+    /// between [`MIN_DISPLAY_WIDTH`](FixedCalculator::MIN_DISPLAY_WIDTH) and
+    /// [`MAX_DISPLAY_WIDTH`](FixedCalculator::MAX_DISPLAY_WIDTH).
+    pub fn set_display_width(&mut self, display_width: u8) {
+        self.engine.set_display_width(display_width);
+    }
+
+    /// Seeds the generator used for random numbers.
+    ///
+    /// The same seed will always produce the same sequence of random numbers.
+    /// Otherwise the generator is seeded from entropy, or from zero without
+    /// the `std` feature.
+    pub fn seed_random(&mut self, seed: u64) {
+        self.engine.seed_random(seed);
+    }
+
+    /// Presses a key.
+    pub fn press(&mut self, key: &Key) {
+        self.engine.press(key);
+    }
+
+    /// Clears the displayed number, keeping the rest of the calculation.
+    ///
+    /// Will perform a deep 'All clear' (AC) operation if the calulator is
+    /// currently cleared.
+    pub fn clear(&mut self) {
+        self.engine.clear();
+    }
+
+    /// Returns the [`FixedNumber`] to be displayed.
+    pub fn display(&self) -> &FixedNumber {
+        self.engine.display()
+    }
+
+    /// Returns the id of the operator that is currently active if it exists.
+    ///
+    /// An operator is active if it is in the last place of the buffer
+    /// and is therefore not succeeded by a number.
+    pub fn active_operator(&self) -> Option<char> {
+        self.engine.active_operator().map(Operation::id)
+    }
+
+    /// Checks if the last operation is remembered for repeated equals.
+    pub fn memory(&self) -> bool {
+        self.engine.memory()
+    }
+
+    /// Performs the 'equals' operation.
+    pub fn submit_equals(&mut self) {
+        self.engine.submit_equals();
+    }
+
+    /// Submit an [`Operator`].
+    ///
+    /// The calculation fails if the buffer has no room for the operator and
+    /// its operand.
+    pub fn submit_operator(&mut self, operator: &Operator) {
+        self.engine
+            .submit_operator(FixedOperator::from_operator(operator));
+    }
+
+    /// Submit a number.
+    ///
+    /// If editing, this will append the number onto the number currently
+    /// being edited.
+    pub fn submit_number(&mut self, number: u8) {
+        self.engine.submit_number(number);
+    }
+
+    /// Performs the 'decimalise' operation.
+    pub fn submit_decimal(&mut self) {
+        self.engine.submit_decimal();
+    }
+
+    /// Performs the 'imaginary' operation. Has no effect in real mode.
+    pub fn submit_imaginary(&mut self) {
+        self.engine.submit_imaginary();
+    }
+
+    /// Performs the 'π' operation.
+    pub fn submit_pi(&mut self) {
+        self.engine.submit_pi();
+    }
+
+    /// Performs the 'e' operation.
+    pub fn submit_e(&mut self) {
+        self.engine.submit_e();
+    }
+
+    /// Performs the 'Rand' operation.
+    pub fn submit_random(&mut self) {
+        self.engine.submit_random();
+    }
+
+    /// Performs the 'EE' operation.
+    pub fn submit_exponent(&mut self) {
+        self.engine.submit_exponent();
+    }
+
+    /// Performs the 'negative' operation.
+    ///
+    /// Negates the exponent if it is being entered.
+    pub fn submit_negative(&mut self) {
+        self.engine.submit_negative();
+    }
+
+    /// Performs the 'percentage' operation.
+    pub fn submit_percentage(&mut self) {
+        self.engine.submit_percentage();
+    }
+
+    /// Applies a [`Function`] to the output.
+    ///
+    /// In complex mode the function's complex form is used.
+    pub fn submit_function(&mut self, function: &Function) {
+        self.engine.submit_function(function);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FixedCalculator;
+    use crate::{
+        calculator::Calculator,
+        key::{format_keys, Key},
+        number::DisplayMode,
+        operator::Operator,
+        random::Random,
+        registry::OperatorRegistry,
+    };
+    use alloc::{string::ToString, vec::Vec};
+
+    /// Creates both calculators, seeded to generate the same random numbers.
+    fn seeded() -> (Calculator, FixedCalculator) {
+        let mut calc = Calculator::new();
+        let mut fixed = FixedCalculator::default();
+        calc.seed_random(0);
+        fixed.seed_random(0);
+        (calc, fixed)
+    }
+
+    /// Checks that both calculators display the same after pressing keys.
+    fn assert_same(calc: &Calculator, fixed: &FixedCalculator, pressed: &[Key]) {
+        let pressed = format_keys(pressed);
+        assert_eq!(
+            fixed.display().to_string(),
+            calc.display().to_string(),
+            "Should display the same after {}",
+            pressed
+        );
+        assert_eq!(
+            fixed.active_operator(),
+            calc.active_operator().map(|operator| operator.id),
+            "Should have the same active operator after {}",
+            pressed
+        );
+        assert_eq!(
+            fixed.cleared(),
+            calc.cleared(),
+            "Should have the same clear key after {}",
+            pressed
+        );
+    }
+
+    /// Presses keys on both calculators, checking that they display the same
+    /// after every key.
+    fn assert_identical(keys: &str, calc: &mut Calculator, fixed: &mut FixedCalculator) {
+        let keys = calc.parse_keys(keys).unwrap();
+        for (index, key) in keys.iter().enumerate() {
+            calc.press(key);
+            fixed.press(key);
+            assert_same(calc, fixed, &keys[..=index]);
+        }
+    }
+
+    /// Checks that a sequence of keys is handled identically by both
+    /// calculators.
+    fn identical(keys: &str) {
+        let (mut calc, mut fixed) = seeded();
+        assert_identical(keys, &mut calc, &mut fixed);
+    }
+
+    #[test]
+    fn arithmetic() {
+        identical("1.2+2==");
+        identical("2+3*4^2-1=");
+        identical("6+*-3=");
+        identical("2^3^2=");
+        identical("9√2={AC}100㏒10=");
+        identical("5*2=+1=");
+        identical("1/0=+1=");
+        identical("1+2√=");
+    }
+
+    #[test]
+    fn powers() {
+        // Powers are resolved from the right, so each is kept in the buffer.
+        identical("1.1^1.1^1.1^1.1^1.1^1.1^3=");
+        identical("2^0.5^0.5^0.5^0.5^0.5^0.5^2=");
+    }
+
+    #[test]
+    fn entry() {
+        identical("0001.2300");
+        identical("1234567891234");
+        identical("1.5±2.");
+        identical("{AC}{AC}±5");
+        identical("12+{AC}3=");
+    }
+
+    #[test]
+    fn exponent() {
+        identical("6.022{EE}23*2=");
+        identical("1{EE}±5±9999");
+        identical("{EE}{EE}3.±");
+        identical("99999999*99999999*99999999*99999999=±{EE}5±");
+    }
+
+    #[test]
+    fn functions_and_constants() {
+        identical("2{²√}{x²}{1/x}");
+        identical("50+10%=");
+        identical("50*10%=");
+        identical("π*e={Rand}+{Rand}=");
+        identical("2/3=±1");
+    }
+
+    #[test]
+    fn complex() {
+        let (mut calc, mut fixed) = seeded();
+        calc.set_complex_mode(true);
+        fixed.set_complex_mode(true);
+
+        assert_identical("4±{²√}+3i=*i=", &mut calc, &mut fixed);
+        assert_identical("∠{Rad}1+i=%±∠", &mut calc, &mut fixed);
+        assert_identical("2i^i={AC}{AC}1+2i㏒i=", &mut calc, &mut fixed);
+    }
+
+    #[test]
+    fn display_settings() {
+        let mut calc = Calculator::new();
+        let mut fixed: FixedCalculator = FixedCalculator::new();
+        calc.set_display_width(15);
+        fixed.set_display_width(15);
+        assert_identical("1234567890123456789/7=", &mut calc, &mut fixed);
+
+        for display_mode in [
+            DisplayMode::Fix(2),
+            DisplayMode::Sci(3),
+            DisplayMode::Eng(1),
+        ] {
+            calc.set_display_mode(display_mode);
+            fixed.set_display_mode(display_mode);
+            assert_identical("{AC}{AC}1/3=*12345=", &mut calc, &mut fixed);
+        }
+    }
+
+    #[test]
+    fn random_keys() {
+        let keys = Calculator::new()
+            .parse_keys("0123456789.+-*/^√㏒{²√}{x²}{1/x}===±%{AC}i∠{Rad}{EE}πe{Rand}")
+            .unwrap();
+
+        let mut random = Random::new(0);
+        for sequence in 0..200 {
+            let (mut calc, mut fixed) = seeded();
+            calc.set_complex_mode(sequence % 2 == 1);
+            fixed.set_complex_mode(sequence % 2 == 1);
+
+            let mut pressed = Vec::new();
+            while pressed.len() < 64 {
+                let key = &keys[(random.next_f64() * keys.len() as f64) as usize];
+                calc.press(key);
+                fixed.press(key);
+                pressed.push(key.clone());
+                assert_same(&calc, &fixed, &pressed);
+            }
+        }
+    }
+
+    #[test]
+    fn full_buffer() {
+        let operators = OperatorRegistry::default();
+        let keys = crate::parse_keys("2^3^2", &operators).unwrap();
+
+        let mut fixed = FixedCalculator::<5>::new();
+        for key in keys.iter() {
+            fixed.press(key);
+        }
+        fixed.submit_equals();
+        assert_eq!(fixed.display().to_string(), "512");
+
+        for key in keys.iter() {
+            fixed.press(key);
+        }
+        fixed.submit_operator(&Operator::power());
+        assert_eq!(
+            fixed.display().to_string(),
+            "Error",
+            "Should fail without room for an operator and its operand."
+        );
+        assert_eq!(fixed.active_operator(), None);
+
+        fixed.submit_number(4);
+        assert_eq!(fixed.display().to_string(), "4");
+    }
+
+    #[test]
+    fn display_width() {
+        let mut fixed: FixedCalculator = FixedCalculator::new();
+        fixed.set_display_width(u8::MAX);
+        assert_eq!(
+            fixed.display_width(),
+            FixedCalculator::<16>::MAX_DISPLAY_WIDTH,
+            "Should limit the display width to the digits stored inline."
+        );
+        fixed.set_display_width(0);
+        assert_eq!(
+            fixed.display_width(),
+            FixedCalculator::<16>::MIN_DISPLAY_WIDTH
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::complex::Complex;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+/// Represents a mathematical function of a single value.
+#[derive(Debug, Clone)]
+pub struct Function {
+    /// The string identifier of the function.
+    pub id: &'static str,
+    /// The function applied to real values.
+    pub function: fn(f64) -> f64,
+    /// The function applied to complex values.
+    pub complex_function: fn(Complex) -> Complex,
+}
+
+impl Function {
+    /// Square root function.
+    pub fn square_root() -> Self {
+        Self {
+            id: "²√",
+            function: |x| x.sqrt(),
+            complex_function: |x| x.sqrt(),
+        }
+    }
+
+    /// Square function.
+    pub fn square() -> Self {
+        Self {
+            id: "x²",
+            function: |x| x * x,
+            complex_function: |x| x * x,
+        }
+    }
+
+    /// Reciprocal function.
+    pub fn reciprocal() -> Self {
+        Self {
+            id: "1/x",
+            function: |x| 1. / x,
+            complex_function: |x| x.recip(),
+        }
+    }
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl TryFrom<&str> for Function {
+    type Error = &'static str;
+
+    /// Create a function from its string identifier.
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::Function;
+    ///
+    /// let square_root = Function::try_from("²√").unwrap();
+    /// let square = Function::try_from("x²").unwrap();
+    /// let reciprocal = Function::try_from("1/x").unwrap();
+    /// ```
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "²√" => Ok(Function::square_root()),
+            "x²" => Ok(Function::square()),
+            "1/x" => Ok(Function::reciprocal()),
+            _ => Err("Unknown function."),
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{error::Error, function::Function, operator::Operator, registry::OperatorRegistry};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+/// A key that can be pressed on the calculator.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Key {
+    /// A number key, see [`Calculator::submit_number`].
+    ///
+    /// [`Calculator::submit_number`]: crate::Calculator::submit_number
+    Number(u8),
+    /// The decimal point key.
+    Decimal,
+    /// An operator key.
+    Operator(Operator),
+    /// A function key.
+    Function(Function),
+    /// The equals key.
+    Equals,
+    /// The sign key.
+    Negative,
+    /// The percentage key.
+    Percentage,
+    /// The clear key.
+    Clear,
+    /// The imaginary unit key.
+    Imaginary,
+    /// The key that toggles between rectangular and polar notation.
+    Polar,
+    /// The key that toggles between degrees and radians.
+    AngleMode,
+    /// The EE key.
+    Exponent,
+    /// The π key.
+    Pi,
+    /// The e key.
+    E,
+    /// The random number key.
+    Random,
+}
+
+impl Key {
+    /// Create a key from its string identifier, resolving operators through
+    /// a registry.
+    ///
+    /// These are the identifiers used as button ids by the web calculator.
+    pub fn from_id(id: &str, operators: &OperatorRegistry) -> Result<Self, Error> {
+        if let [digit @ b'0'..=b'9'] = id.as_bytes() {
+            return Ok(Key::Number(digit - b'0'));
+        }
+        if let Some(operator) = operators.get(id) {
+            return Ok(Key::Operator(operator.clone()));
+        }
+        if let Ok(function) = Function::try_from(id) {
+            return Ok(Key::Function(function));
+        }
+
+        match id {
+            "." => Ok(Key::Decimal),
+            "=" => Ok(Key::Equals),
+            "±" => Ok(Key::Negative),
+            "%" => Ok(Key::Percentage),
+            "c" | "C" | "AC" => Ok(Key::Clear),
+            "i" => Ok(Key::Imaginary),
+            "∠" => Ok(Key::Polar),
+            "Rad" => Ok(Key::AngleMode),
+            "EE" => Ok(Key::Exponent),
+            "π" => Ok(Key::Pi),
+            "e" => Ok(Key::E),
+            "Rand" => Ok(Key::Random),
+            _ => Err(Error::UnknownKey(id.to_string())),
+        }
+    }
+
+    /// Returns the string identifier of the key.
+    pub fn id(&self) -> String {
+        match self {
+            Key::Number(number) => number.to_string(),
+            Key::Operator(operator) => operator.id.to_string(),
+            Key::Function(function) => function.id.to_string(),
+            Key::Decimal => String::from("."),
+            Key::Equals => String::from("="),
+            Key::Negative => String::from("±"),
+            Key::Percentage => String::from("%"),
+            Key::Clear => String::from("AC"),
+            Key::Imaginary => String::from("i"),
+            Key::Polar => String::from("∠"),
+            Key::AngleMode => String::from("Rad"),
+            Key::Exponent => String::from("EE"),
+            Key::Pi => String::from("π"),
+            Key::E => String::from("e"),
+            Key::Random => String::from("Rand"),
+        }
+    }
+}
+
+impl Display for Key {
+    /// Formats the key in the key sequence format, see [`parse_keys`].
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let id = self.id();
+        if id.chars().count() == 1 {
+            write!(f, "{}", id)
+        } else {
+            write!(f, "{{{}}}", id)
+        }
+    }
+}
+
+/// Parses a sequence of keys.
+///
+/// Each character is the identifier of a key, such as `1`, `+` or `=`.
+/// Keys with longer identifiers are wrapped in braces, such as `{AC}`,
+/// `{EE}` or `{1/x}`. Whitespace is ignored.
+///
+/// Example
+/// ```rust
+/// use ios_calculator::{parse_keys, Key, Operator, OperatorRegistry};
+///
+/// let keys = parse_keys("1.2 + 2 = {AC}", &OperatorRegistry::default()).unwrap();
+///
+/// assert_eq!(
+///     keys,
+///     vec![
+///         Key::Number(1),
+///         Key::Decimal,
+///         Key::Number(2),
+///         Key::Operator(Operator::add()),
+///         Key::Number(2),
+///         Key::Equals,
+///         Key::Clear,
+///     ]
+/// );
+/// ```
+pub fn parse_keys(input: &str, operators: &OperatorRegistry) -> Result<Vec<Key>, Error> {
+    let mut keys = Vec::new();
+    let mut chars = input.chars();
+
+    while let Some(char) = chars.next() {
+        if char.is_whitespace() {
+            continue;
+        }
+        let id = if char == '{' {
+            let mut id = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') => break id,
+                    Some(char) => id.push(char),
+                    None => return Err(Error::UnknownKey(format!("{{{}", id))),
+                }
+            }
+        } else {
+            char.to_string()
+        };
+        keys.push(Key::from_id(&id, operators)?);
+    }
+    Ok(keys)
+}
+
+/// Formats a sequence of keys, so that it can be parsed by [`parse_keys`].
+pub fn format_keys(keys: &[Key]) -> String {
+    keys.iter().map(|key| key.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_keys, parse_keys, Key};
+    use crate::{error::Error, function::Function, operator::Operator, registry::OperatorRegistry};
+    use alloc::string::String;
+
+    #[test]
+    fn parse_special_keys() {
+        let keys = parse_keys("{AC}{±}{x²}{EE}", &OperatorRegistry::default()).unwrap();
+        assert_eq!(
+            keys,
+            vec![
+                Key::Clear,
+                Key::Negative,
+                Key::Function(Function::square()),
+                Key::Exponent,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_number() {
+        let keys = parse_keys("10", &OperatorRegistry::default()).unwrap();
+        assert_eq!(keys, vec![Key::Number(1), Key::Number(0)]);
+
+        for id in ["10", "255", "07", "-1"] {
+            assert_eq!(
+                parse_keys(&format!("{{{}}}", id), &OperatorRegistry::default()),
+                Err(Error::UnknownKey(String::from(id))),
+                "Should only accept the number keys 0 to 9."
+            );
+        }
+    }
+
+    #[test]
+    fn parse_unknown_key() {
+        assert_eq!(
+            parse_keys("1{foo}", &OperatorRegistry::default()),
+            Err(Error::UnknownKey(String::from("foo")))
+        );
+        assert_eq!(
+            parse_keys("1#", &OperatorRegistry::default()),
+            Err(Error::UnknownKey(String::from("#")))
+        );
+    }
+
+    #[test]
+    fn parse_unterminated() {
+        assert_eq!(
+            parse_keys("1{AC", &OperatorRegistry::default()),
+            Err(Error::UnknownKey(String::from("{AC")))
+        );
+    }
+
+    #[test]
+    fn format_round_trip() {
+        let keys = vec![
+            Key::Number(1),
+            Key::Operator(Operator::multiply()),
+            Key::Number(2),
+            Key::Clear,
+            Key::Function(Function::reciprocal()),
+            Key::Equals,
+        ];
+        let formatted = format_keys(&keys);
+
+        assert_eq!(formatted, "1*2{AC}{1/x}=");
+        assert_eq!(
+            parse_keys(&formatted, &OperatorRegistry::default()),
+            Ok(keys)
+        );
+    }
+}
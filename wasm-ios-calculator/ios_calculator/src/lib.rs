@@ -1,11 +1,17 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 //! # Rusty iOS Calculator
 //! 
 //! An implementation of the basic iOS calculator featuring:
 //! - DMAS (division, muliplication, addition, subtraction) operations
+//! - Exponents, roots and logarithms
 //! - Decimalisation
 //! - Memory of last calculation
 //! - Percentage conversion
+//! - Complex numbers
+//! - `no_std` support, with `alloc`, by disabling the default `std` feature
+//!   and enabling `libm`
+//! - A fixed capacity [`FixedCalculator`] that doesn't allocate
 //! 
 //! ### Example
 //! ```rust
@@ -21,20 +27,71 @@
 //! calc.submit_equals();
 //! 
 //! assert_eq!(
-//!     calc.output().value(),
+//!     calc.display().value(),
 //!     3.2,
 //! );
 //! 
 //! assert_eq!(
-//!     calc.output().to_string(),
+//!     calc.display().to_string(),
 //!     "3.2",
 //! ); 
 //! ```
+extern crate alloc;
+
+// Tests use the standard library's macros, even without the `std` feature.
+#[cfg(test)]
+#[macro_use]
+extern crate std;
+
 mod calculator;
 pub use calculator::Calculator;
 
 mod operator;
-pub use operator::Operator;
+pub use operator::{Associativity, Operator};
+
+mod error;
+pub use error::Error;
+
+mod engine;
+
+mod evaluator;
+
+mod fixed;
+pub use fixed::{FixedCalculator, FixedNumber, InlineText};
+
+mod function;
+pub use function::Function;
+
+mod key;
+pub use key::{format_keys, parse_keys, Key};
+
+mod complex;
+pub use complex::Complex;
+
+mod angle;
+pub use angle::AngleMode;
 
 mod number;
+pub use number::{DisplayMode, Number};
+
+mod random;
+
+mod speech;
+pub use speech::speak;
+
+mod registry;
+pub use registry::OperatorRegistry;
+
+mod storage;
+pub use storage::Text;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("Enable the `libm` feature for floating point functions without `std`.");
+
+#[cfg(not(any(feature = "std", test)))]
+mod math;
+
 mod truncate;
+
+mod view;
+pub use view::ViewModel;
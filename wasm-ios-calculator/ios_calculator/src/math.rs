@@ -0,0 +1,83 @@
+//! Floating point functions for `no_std` builds, which are provided by `libm`
+//! rather than the standard library.
+
+/// The `f64` methods the calculator uses that are only available with `std`.
+pub trait Float {
+    fn sqrt(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn log10(self) -> Self;
+    fn log2(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn floor(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn fract(self) -> Self;
+}
+
+impl Float for f64 {
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        libm::pow(self, n)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        libm::pow(self, n.into())
+    }
+
+    fn exp(self) -> Self {
+        libm::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        libm::log(self)
+    }
+
+    fn log10(self) -> Self {
+        libm::log10(self)
+    }
+
+    fn log2(self) -> Self {
+        libm::log2(self)
+    }
+
+    fn sin(self) -> Self {
+        libm::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        libm::cos(self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        libm::atan2(self, other)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        libm::hypot(self, other)
+    }
+
+    fn floor(self) -> Self {
+        libm::floor(self)
+    }
+
+    fn round(self) -> Self {
+        libm::round(self)
+    }
+
+    fn trunc(self) -> Self {
+        libm::trunc(self)
+    }
+
+    fn fract(self) -> Self {
+        self - libm::trunc(self)
+    }
+}
@@ -1,140 +1,696 @@
 use super::truncate::Truncate;
-use num_format::{Locale, ToFormattedString};
-use std::{cmp, fmt::Display};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{angle::AngleMode, complex::Complex, speech::speak, storage::Text};
+use alloc::string::{String, ToString};
+use arrayvec::ArrayString;
+use core::{
+    cmp,
+    fmt::{self, Display, Write},
+};
+
+/// The maximum number of digits that can be entered into an exponent.
+pub(crate) const MAX_EXPONENT_LEN: usize = 3;
+
+/// The length of the longest value in exponential notation, such as
+/// `-2.2250738585072014e-308`.
+const EXPONENTIAL_LEN: usize = 24;
+
+/// The notation used to display complex numbers.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ComplexNotation {
+    /// Displayed as `a+bi`.
+    #[default]
+    Rectangular,
+    /// Displayed as `r∠θ`, with the angle in the given unit.
+    Polar(AngleMode),
+}
+
+/// The mode used to display real values.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DisplayMode {
+    /// Displayed as entered, switching to exponential notation for very large
+    /// or small values.
+    #[default]
+    Normal,
+    /// Displayed rounded to a fixed number of decimal places, e.g. `1.50`.
+    Fix(u8),
+    /// Displayed in scientific notation with a fixed number of decimal places
+    /// in the coefficient, e.g. `1.23e4`.
+    Sci(u8),
+    /// Displayed in engineering notation, with an exponent that is a multiple
+    /// of three and a fixed number of decimal places in the coefficient,
+    /// e.g. `12.3e3`.
+    Eng(u8),
+}
+
+/// The digits of a [`Number`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Digits<T> {
+    /// The digits as entered, permitting leading and trailing zeroes.
+    Entered(T),
+    /// The digits of the shortest string value of a value, which are only
+    /// stored once they are entered onto.
+    Shortest(f64),
+}
+
+impl<T: Text> Digits<T> {
+    /// Returns the numeric value of the digits.
+    fn value(&self) -> f64 {
+        match self {
+            Digits::Entered(text) => text.parse().unwrap(),
+            Digits::Shortest(value) => *value,
+        }
+    }
+
+    /// Returns the digits so that they can be entered onto, storing the
+    /// shortest string value if needed.
+    ///
+    /// Returns `None` if the digits don't fit in the text.
+    fn entered(&mut self) -> Option<&mut T> {
+        if let Digits::Shortest(value) = *self {
+            let mut text = T::default();
+            write!(text, "{}", value).ok()?;
+            *self = Digits::Entered(text);
+        }
+        match self {
+            Digits::Entered(text) => Some(text),
+            Digits::Shortest(..) => None,
+        }
+    }
+}
+
+impl<T: Text> Display for Digits<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Digits::Entered(text) => f.write_str(text),
+            Digits::Shortest(value) => Display::fmt(value, f),
+        }
+    }
+}
 
 /// Represents a number.
 ///
 /// Stores both a floating point value and string value of a number.
-/// The string value permits leading and trailing zeroes to be displayed.
-#[derive(Debug, PartialEq, Clone)]
-pub struct Number {
+/// The string value permits leading and trailing zeroes to be displayed, and
+/// is stored as [`Text`], which is a [`String`] unless the number is a
+/// [`FixedNumber`](crate::FixedNumber).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Number<T = String> {
     // The floating point value of the number.
     value: f64,
+    // The floating point value of the imaginary part of the number.
+    imaginary: f64,
     // The string value of the number.
-    value_str: String,
+    value_str: Digits<T>,
+    // The string value of the exponent, when entering scientific notation.
+    exponent_str: Option<T>,
     // The number of significant figures used for formatting.
     sf: Option<u8>,
+    // The notation used when formatting a complex number.
+    notation: ComplexNotation,
+    // The mode used when formatting a real value.
+    display_mode: DisplayMode,
+    // Whether the number is being edited, so is formatted as entered rather
+    // than in the display mode.
+    editing: bool,
 }
 
-impl Number {
+impl<T: Text> Number<T> {
+    /// Create a number with a complex value, however its string value is
+    /// stored.
+    pub(crate) fn from_complex(value: Complex) -> Self {
+        Number {
+            value: value.re,
+            imaginary: value.im,
+            value_str: Digits::Shortest(value.re),
+            exponent_str: None,
+            sf: Some(9),
+            notation: ComplexNotation::default(),
+            display_mode: DisplayMode::default(),
+            editing: false,
+        }
+    }
+
     /// Returns the numeric value of the number.
+    ///
+    /// For a complex number this is the real part.
     pub fn value(&self) -> f64 {
         self.value
     }
 
+    /// Returns the numeric value of the imaginary part of the number.
+    pub fn imaginary(&self) -> f64 {
+        self.imaginary
+    }
+
+    /// Returns the complex value of the number.
+    pub fn complex(&self) -> Complex {
+        Complex::new(self.value, self.imaginary)
+    }
+
+    /// Checks if the number has no imaginary part.
+    pub fn is_real(&self) -> bool {
+        self.imaginary == 0.
+    }
+
+    /// Returns the number as it would be read aloud in English.
+    pub fn spoken(&self) -> String {
+        speak(&self.to_string())
+    }
+
+    /// Checks if the number is an error, such as the result of dividing by zero.
+    pub fn is_error(&self) -> bool {
+        !self.value.is_finite() || !self.imaginary.is_finite()
+    }
+
     /// Sets the value of the number.
     ///
-    /// Updates both the numeric and string value.
+    /// Updates both the numeric and string value, removing any imaginary part.
     pub fn set_value(&mut self, value: f64) {
         self.value = value;
-        self.value_str = value.to_string();
+        self.imaginary = 0.;
+        self.value_str = Digits::Shortest(value);
+        self.exponent_str = None;
+    }
+
+    /// Sets the complex value of the number.
+    pub fn set_complex(&mut self, value: Complex) {
+        self.set_value(value.re);
+        self.imaginary = value.im;
+    }
+
+    /// Sets the notation used when formatting a complex number.
+    pub fn set_notation(&mut self, notation: ComplexNotation) {
+        self.notation = notation;
+    }
+
+    /// Sets the number of significant figures used for entry and formatting.
+    pub fn set_sf(&mut self, sf: Option<u8>) {
+        self.sf = sf;
+    }
+
+    /// Sets the mode used when formatting a real value.
+    pub fn set_display_mode(&mut self, display_mode: DisplayMode) {
+        self.display_mode = display_mode;
+    }
+
+    /// Sets whether the number is being edited.
+    ///
+    /// A number being edited is formatted as entered, ignoring the display
+    /// mode, so that each digit typed is shown. Entering digits, a decimal
+    /// point or an exponent starts editing.
+    pub fn set_editing(&mut self, editing: bool) {
+        self.editing = editing;
+    }
+
+    /// Multiplies the number by the imaginary unit.
+    pub fn imaginarise(&mut self) {
+        self.set_complex(self.complex() * Complex::I);
+    }
+
+    /// Checks if the exponent of the number is being entered.
+    pub fn entering_exponent(&self) -> bool {
+        self.exponent_str.is_some()
+    }
+
+    /// Starts entering the exponent of the number.
+    ///
+    /// Subsequent appended numbers are appended onto the exponent.
+    pub fn start_exponent(&mut self) {
+        self.editing = true;
+        if self.exponent_str.is_none() {
+            self.clear_error();
+            let mut exponent_str = T::default();
+            let _ = exponent_str.write_char('0');
+            self.exponent_str = Some(exponent_str);
+        }
+    }
+
+    /// Starts a new number if the value can't be entered onto, such as the
+    /// result of dividing by zero.
+    fn clear_error(&mut self) {
+        if self.is_error() {
+            self.set_value(0.);
+        }
+    }
+
+    /// Negates the exponent being entered.
+    pub fn negate_exponent(&mut self) {
+        self.editing = true;
+        if let Some(exponent_str) = self.exponent_str.as_mut() {
+            if exponent_str.starts_with('-') {
+                exponent_str.remove(0);
+            } else {
+                let _ = exponent_str.insert(0, '-');
+            }
+            self.update_exponential_value();
+        }
+    }
+
+    /// Sets the numeric value from the coefficient and exponent strings.
+    fn update_exponential_value(&mut self) {
+        let Some(exponent_str) = &self.exponent_str else {
+            return;
+        };
+
+        // The coefficient in exponential notation has the same digits, so
+        // its exponent can be offset instead.
+        let mut coefficient = ArrayString::<EXPONENTIAL_LEN>::new();
+        let _ = write!(coefficient, "{:e}", self.value_str.value());
+        let (coefficient, exponent) = coefficient.split_once('e').unwrap();
+        let exponent = exponent.parse::<i32>().unwrap() + exponent_str.parse::<i32>().unwrap();
+
+        let mut exponential = ArrayString::<{ EXPONENTIAL_LEN + 2 }>::new();
+        let _ = write!(exponential, "{}e{}", coefficient, exponent);
+        self.value = exponential.parse().unwrap();
     }
 
     /// Append a decimal point to the number if valid.
     pub fn decimalise(&mut self) {
-        if self.value.fract() != 0. || self.value_str.ends_with('.') {
+        self.editing = true;
+        if self.entering_exponent() || self.value.fract() != 0. {
             return;
         }
-        self.value_str.push('.');
+        if let Some(value_str) = self.value_str.entered() {
+            if !value_str.contains('.') {
+                let _ = value_str.write_char('.');
+            }
+        }
     }
 
     /// Append a number onto the end of the number.
+    ///
+    /// If the exponent is being entered, the number is appended onto the
+    /// exponent.
     pub fn append(&mut self, number: u8) {
+        self.editing = true;
+        if let Some(exponent_str) = self.exponent_str.as_mut() {
+            let digits = exponent_str.trim_start_matches('-');
+            if digits == "0" {
+                exponent_str.truncate(exponent_str.len() - 1);
+            } else if digits.len() >= MAX_EXPONENT_LEN {
+                // Can't append a new number beyond the maximum exponent length.
+                return;
+            }
+            let _ = write!(exponent_str, "{}", number);
+            self.update_exponential_value();
+            return;
+        }
+        self.clear_error();
         if let Some(sf) = self.sf {
             // Can't append a new number beyond the number of significant figures.
-            if self.value_str.len() == sf.into() {
+            if count_digits(&self.value_str).count >= sf.into() {
                 return;
             }
         }
-        if self.value_str == "0" {
-            self.value_str = number.to_string();
-        } else if self.value_str == "-0" {
-            self.value_str = format!("-{}", number);
-        } else {
-            self.value_str.push_str(&number.to_string());
+        let Some(value_str) = self.value_str.entered() else {
+            return;
+        };
+        if &**value_str == "0" {
+            value_str.truncate(0);
+        } else if &**value_str == "-0" {
+            value_str.truncate(1);
         }
+        let _ = write!(value_str, "{}", number);
 
         // Set the numeric value from the updated string value.
-        self.value = self.value_str.parse().unwrap();
+        self.value = value_str.parse().unwrap();
     }
 }
 
-impl<T: Into<f64> + Display + Copy> From<T> for Number {
-    fn from(value: T) -> Self {
-        Number {
-            value: value.into(),
-            value_str: value.to_string(),
-            sf: Some(9),
+impl<V: Into<f64>> From<V> for Number {
+    fn from(value: V) -> Self {
+        Number::from_complex(Complex::from(value.into()))
+    }
+}
+
+impl From<Complex> for Number {
+    fn from(value: Complex) -> Self {
+        Number::from_complex(value)
+    }
+}
+
+/// Counts the digits written to it.
+#[derive(Default)]
+struct DigitCounter {
+    /// The number of digits.
+    count: usize,
+    /// The number of digits before any decimal point.
+    integer_len: usize,
+    /// Has a decimal point been written?
+    fraction: bool,
+}
+
+impl Write for DigitCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c == '.' {
+                self.fraction = true;
+            } else if c.is_ascii_digit() {
+                self.count += 1;
+                if !self.fraction {
+                    self.integer_len += 1;
+                }
+            }
         }
+        Ok(())
     }
 }
 
-impl Display for Number {
-    /// Formats the number.
-    ///
-    /// TODO: localise format, for example French format uses comma as decimal point.
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Convert to exponential notation.
-        let exponential_format = format!("{:e}", self.value);
+/// Counts the digits of a formatted value.
+fn count_digits(value: &dyn Display) -> DigitCounter {
+    let mut counter = DigitCounter::default();
+    let _ = write!(counter, "{}", value);
+    counter
+}
 
-        // Split exponential into coefficient and exponential parts.
-        let mut split = exponential_format.split('e');
-        let coefficient_str = split.next().unwrap();
-        let exponent_str = split.next().unwrap();
+/// Checks if the text written to it is some expected text.
+struct Matches<'a>(Option<&'a str>);
 
-        // Parse exponent into float.
-        let exponent: f64 = exponent_str.parse().unwrap();
+impl Write for Matches<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 = self.0.and_then(|rest| rest.strip_prefix(s));
+        Ok(())
+    }
+}
 
-        if let Some(sf) = self.sf {
-            // If the exponent is sufficiently large or small, then output exponential notation.
-            if exponent.abs() >= sf.into() {
-                let exponent_str_len = exponent.to_string().len() as u8;
+/// Checks if a value is formatted as some text.
+fn formats_as(value: &dyn Display, text: &str) -> bool {
+    let mut matches = Matches(Some(text));
+    let _ = write!(matches, "{}", value);
+    matches.0 == Some("")
+}
+
+/// Writes the digits of a formatted value with commas separating the
+/// thousands, truncated to a number of significant figures.
+struct Grouped<'a> {
+    /// Where the digits are written.
+    output: &'a mut dyn Write,
+    /// The number of integer digits.
+    integer_len: usize,
+    /// The number of integer digits written so far.
+    integer_written: usize,
+    /// Has the decimal point been written?
+    fraction: bool,
+    /// The number of digits that can still be written, if limited.
+    remaining: Option<usize>,
+}
 
-                // Calculate the number of digits that should be displayed in the coefficient.
-                let max_coefficient_len = cmp::max(1, sf - (exponent_str_len + 1));
+impl Write for Grouped<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.remaining == Some(0) {
+                break;
+            }
+            if c == '.' {
+                self.fraction = true;
+            } else if c.is_ascii_digit() {
+                if !self.fraction {
+                    if self.integer_written > 0
+                        && (self.integer_len - self.integer_written).is_multiple_of(3)
+                    {
+                        self.output.write_char(',')?;
+                    }
+                    self.integer_written += 1;
+                }
+                self.remaining = self.remaining.map(|remaining| remaining - 1);
+            }
+            self.output.write_char(c)?;
+        }
+        Ok(())
+    }
+}
 
-                let trunc_coefficient = coefficient_str.truncate_nums(max_coefficient_len.into());
+/// Writes the digits of a formatted value, as entered.
+///
+/// The integer digits are separated by commas, and the digits are truncated
+/// to the number of significant figures.
+fn write_grouped(f: &mut dyn Write, digits: &dyn Display, sf: Option<u8>) -> fmt::Result {
+    let mut grouped = Grouped {
+        output: f,
+        integer_len: count_digits(digits).integer_len,
+        integer_written: 0,
+        fraction: false,
+        // As with `truncate_nums`, a limit of zero has no effect.
+        remaining: sf.filter(|&sf| sf > 0).map(usize::from),
+    };
+    write!(grouped, "{}", digits)
+}
 
-                /* Exponential notation */
-                return write!(f, "{}e{}", trunc_coefficient, exponent_str);
+/// Formats a real value in the given display mode.
+///
+/// The digits are used in the normal mode, so that they are shown as
+/// entered.
+fn format_real(
+    f: &mut dyn Write,
+    value: f64,
+    digits: &dyn Display,
+    sf: Option<u8>,
+    display_mode: DisplayMode,
+) -> fmt::Result {
+    if !value.is_finite() {
+        return f.write_str("Error");
+    }
+
+    match display_mode {
+        DisplayMode::Normal => format_normal(f, value, digits, sf),
+        DisplayMode::Fix(decimals) => {
+            let mut decimals = usize::from(decimals);
+            if let Some(sf) = sf {
+                // Too many integer digits to display, fall back to the normal format.
+                if value.abs() >= 10_f64.powi(sf.into()) {
+                    return format_normal(f, value, &value, Some(sf));
+                }
+                // The decimal places are only shown if they all fit after
+                // the integer digits.
+                if count_digits(&value.trunc()).integer_len + decimals > sf.into() {
+                    decimals = 0;
+                }
+                // Rounding can carry over into another integer digit.
+                let rounded = count_digits(&format_args!("{:.*}", decimals, value));
+                if rounded.integer_len + decimals > sf.into() {
+                    if decimals == 0 {
+                        return format_normal(f, value, &value, Some(sf));
+                    }
+                    decimals = 0;
+                }
             }
+
+            if decimals == 0 {
+                // Rounding to an integer can carry over into exponential
+                // notation.
+                format_normal(f, value.round(), &format_args!("{:.0}", value), sf)
+            } else {
+                write_grouped(f, &format_args!("{:.*}", decimals, value), sf)
+            }
+        }
+        DisplayMode::Sci(decimals) => format_exponential(f, value, decimals, 1, sf),
+        DisplayMode::Eng(decimals) => format_exponential(f, value, decimals, 3, sf),
+    }
+}
+
+/// Formats a real value in exponential notation, where the exponent is a
+/// multiple of `step`.
+///
+/// The number of decimal places is reduced if the output would exceed the
+/// number of significant figures.
+fn format_exponential(
+    f: &mut dyn Write,
+    value: f64,
+    decimals: u8,
+    step: i32,
+    sf: Option<u8>,
+) -> fmt::Result {
+    let render = |exponent: i32| {
+        let coefficient = value / 10_f64.powi(exponent);
+        let mut decimals = usize::from(decimals);
+
+        if let Some(sf) = sf {
+            let int_len = count_digits(&(coefficient.abs().trunc() as i64)).count;
+            let exponent_len = count_digits(&exponent.unsigned_abs()).count;
+            decimals = cmp::min(
+                decimals,
+                usize::from(sf).saturating_sub(int_len + exponent_len),
+            );
         }
+        (coefficient, decimals)
+    };
+
+    let mut exponent = if value == 0. {
+        0
+    } else {
+        (value.abs().log10().floor() as i32).div_euclid(step) * step
+    };
+    let (mut coefficient, mut decimals) = render(exponent);
+
+    // Rounding can carry the coefficient over to the next exponent.
+    let rounded = count_digits(&format_args!("{:.*}", decimals, coefficient));
+    if rounded.integer_len > step.unsigned_abs() as usize {
+        exponent += step;
+        (coefficient, decimals) = render(exponent);
+    }
+
+    write!(f, "{:.*}e{}", decimals, coefficient, exponent)
+}
+
+/// Formats a real value, as entered.
+///
+/// The digits are used rather than the value so that leading and trailing
+/// zeroes are displayed.
+fn format_normal(
+    f: &mut dyn Write,
+    value: f64,
+    digits: &dyn Display,
+    sf: Option<u8>,
+) -> fmt::Result {
+    if let Some(sf) = sf {
+        // Convert to exponential notation.
+        let mut exponential_format = ArrayString::<EXPONENTIAL_LEN>::new();
+        write!(exponential_format, "{:e}", value)?;
+
+        // Split exponential into coefficient and exponential parts.
+        let (coefficient_str, exponent_str) = exponential_format.split_once('e').unwrap();
+        let exponent: i32 = exponent_str.parse().unwrap();
+
+        // If the exponent is sufficiently large or small, then output exponential notation.
+        if exponent.unsigned_abs() >= sf.into() {
+            // Calculate the number of digits that should be displayed in the coefficient.
+            let max_coefficient_len = cmp::max(1, sf.saturating_sub(exponent_str.len() as u8 + 1));
+
+            let trunc_coefficient = coefficient_str.truncate_nums(max_coefficient_len.into());
+
+            /* Exponential notation */
+            return write!(f, "{}e{}", trunc_coefficient, exponent_str);
+        }
+    }
+
+    write_grouped(f, digits, sf)
+}
+
+/// A real value formatted in a display mode, such as a component of a complex
+/// number.
+struct Real {
+    value: f64,
+    sf: Option<u8>,
+    display_mode: DisplayMode,
+}
 
-        // Formatted output string to be returned.
-        let mut formatted_output = String::new();
+impl Display for Real {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format_real(f, self.value, &self.value, self.sf, self.display_mode)
+    }
+}
 
-        // Get integer component.
-        let int = self.value().trunc() as i64;
+/// Formats the two components of a complex number, sharing the significant
+/// figure budget between them.
+fn format_components(a: f64, b: f64, sf: Option<u8>, display_mode: DisplayMode) -> (Real, Real) {
+    (
+        Real {
+            value: a,
+            sf: sf.map(|sf| sf - sf / 2),
+            display_mode,
+        },
+        Real {
+            value: b,
+            sf: sf.map(|sf| sf / 2),
+            display_mode,
+        },
+    )
+}
 
-        // Edge case where zero is negative, preserve negative sign.
-        if int == 0 && self.value.is_sign_negative() {
-            formatted_output.push('-');
+impl<T: Text> Display for Number<T> {
+    /// Formats the number.
+    ///
+    /// Complex numbers are formatted in the number's [`ComplexNotation`].
+    ///
+    /// TODO: localise format, for example French format uses comma as decimal point.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_error() {
+            return write!(f, "Error");
         }
 
-        // Push formatted integer component.
-        formatted_output.push_str(&int.to_formatted_string(&Locale::en).to_string());
+        // Numbers being edited are shown as entered.
+        let display_mode = if self.editing {
+            DisplayMode::Normal
+        } else {
+            self.display_mode
+        };
 
-        // Split string at the decimal point.
-        let split: Vec<&str> = self.value_str.split('.').collect();
+        if let Some(exponent_str) = self.exponent_str.as_ref().filter(|_| self.editing) {
+            // Share the significant figures between the coefficient and exponent.
+            let exponent_len = exponent_str.trim_start_matches('-').len() as u8;
+            let sf = self
+                .sf
+                .map(|sf| cmp::max(1, sf.saturating_sub(exponent_len)));
+            format_normal(f, self.value_str.value(), &self.value_str, sf)?;
 
-        // Push formatted fractional component.
-        if let Some(fract) = split.get(1) {
-            formatted_output.push('.');
-            formatted_output.push_str(fract);
+            /* Exponential notation, as entered */
+            return write!(f, "e{}", &**exponent_str);
         }
 
-        // Truncate string
-        if let Some(sf) = self.sf {
-            return write!(f, "{}", formatted_output.truncate_nums(sf.into()));
+        if self.is_real() {
+            return match self.exponent_str {
+                // The string value is only the coefficient of an exponent
+                // that has been entered.
+                Some(..) => format_real(f, self.value, &self.value, self.sf, display_mode),
+                None => format_real(f, self.value, &self.value_str, self.sf, display_mode),
+            };
         }
 
-        write!(f, "{}", formatted_output)
+        match self.notation {
+            ComplexNotation::Rectangular => {
+                let sign = if self.imaginary.is_sign_negative() {
+                    "-"
+                } else {
+                    "+"
+                };
+
+                let imaginary = if self.value == 0. {
+                    if sign == "-" {
+                        f.write_str(sign)?;
+                    }
+                    Real {
+                        value: self.imaginary.abs(),
+                        sf: self.sf,
+                        display_mode,
+                    }
+                } else {
+                    let (real, imaginary) =
+                        format_components(self.value, self.imaginary.abs(), self.sf, display_mode);
+                    write!(f, "{}{}", real, sign)?;
+                    imaginary
+                };
+
+                // The coefficient of the imaginary unit is omitted if it is one.
+                if !formats_as(&imaginary, "1") {
+                    write!(f, "{}", imaginary)?;
+                }
+                f.write_str("i")
+            }
+            ComplexNotation::Polar(angle_mode) => {
+                let complex = self.complex();
+                let (modulus, argument) = format_components(
+                    complex.modulus(),
+                    angle_mode.from_radians(complex.argument()),
+                    self.sf,
+                    display_mode,
+                );
+                let unit = match angle_mode {
+                    AngleMode::Degrees => "°",
+                    AngleMode::Radians => "",
+                };
+                write!(f, "{}∠{}{}", modulus, argument, unit)
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Number;
+    use super::{ComplexNotation, DisplayMode, Number};
+    use crate::{angle::AngleMode, complex::Complex};
+    use alloc::string::{String, ToString};
 
     #[test]
     fn append() {
@@ -160,6 +716,20 @@ mod tests {
         assert_eq!(number.to_string(), "1.", "Should add decimal to end of number string.");
     }
 
+    #[test]
+    fn decimalise_trailing_zeroes() {
+        let mut number = Number::from(1);
+        number.decimalise();
+        number.append(0);
+        number.decimalise();
+        number.append(5);
+        assert_eq!(
+            number.to_string(),
+            "1.05",
+            "Should not add a second decimal after trailing zeroes."
+        );
+    }
+
     #[test]
     fn formats_trailing_zeroes() {
         let mut number = Number::from(1);
@@ -209,4 +779,342 @@ mod tests {
         let number = Number::from(-0.00000000123456789);
         assert_eq!(format!("{}", number), "-1.23456e-9");
     }
+
+    #[test]
+    fn formats_not_a_number() {
+        let number = Number::from(f64::NAN);
+        assert_eq!(format!("{}", number), "Error");
+    }
+
+    #[test]
+    fn formats_complex_error() {
+        let number = Number::from(Complex::new(f64::NAN, f64::NAN));
+        assert_eq!(format!("{}", number), "Error");
+
+        let mut number = Number::from(Complex::new(1., f64::INFINITY));
+        number.set_notation(ComplexNotation::Polar(AngleMode::Degrees));
+        assert_eq!(
+            format!("{}", number),
+            "Error",
+            "Should format an error the same in every notation."
+        );
+    }
+
+    #[test]
+    fn formats_imaginary() {
+        let number = Number::from(Complex::new(0., 2.));
+        assert_eq!(format!("{}", number), "2i");
+    }
+
+    #[test]
+    fn formats_imaginary_unit() {
+        let number = Number::from(Complex::new(0., -1.));
+        assert_eq!(format!("{}", number), "-i");
+    }
+
+    #[test]
+    fn formats_rectangular() {
+        let number = Number::from(Complex::new(1.5, -2.));
+        assert_eq!(format!("{}", number), "1.5-2i");
+    }
+
+    #[test]
+    fn formats_rectangular_truncation() {
+        let number = Number::from(Complex::new(1. / 3., 2. / 3.));
+        assert_eq!(
+            format!("{}", number),
+            "0.3333+0.666i",
+            "Should share the significant figures between both parts."
+        );
+    }
+
+    #[test]
+    fn formats_polar_degrees() {
+        let mut number = Number::from(Complex::new(0., 2.));
+        number.set_notation(ComplexNotation::Polar(AngleMode::Degrees));
+        assert_eq!(format!("{}", number), "2∠90°");
+    }
+
+    #[test]
+    fn formats_polar_radians() {
+        let mut number = Number::from(Complex::new(0., -2.));
+        number.set_notation(ComplexNotation::Polar(AngleMode::Radians));
+        assert_eq!(format!("{}", number), "2∠-1.570");
+    }
+
+    #[test]
+    fn formats_fix() {
+        let mut number = Number::from(1234.567);
+        number.set_display_mode(DisplayMode::Fix(2));
+        assert_eq!(format!("{}", number), "1,234.57");
+    }
+
+    #[test]
+    fn formats_fix_pads_zeroes() {
+        let mut number = Number::from(1.5);
+        number.set_display_mode(DisplayMode::Fix(2));
+        assert_eq!(format!("{}", number), "1.50");
+    }
+
+    #[test]
+    fn formats_fix_rounds_up() {
+        let mut number = Number::from(-1.999);
+        number.set_display_mode(DisplayMode::Fix(2));
+        assert_eq!(format!("{}", number), "-2.00");
+    }
+
+    #[test]
+    fn formats_fix_large() {
+        let mut number = Number::from(1234567890.);
+        number.set_display_mode(DisplayMode::Fix(2));
+        assert_eq!(
+            format!("{}", number),
+            "1.234567e9",
+            "Should fall back to exponential notation."
+        );
+    }
+
+    #[test]
+    fn formats_fix_budget() {
+        let mut number = Number::from(99_999_999.);
+        number.set_display_mode(DisplayMode::Fix(2));
+        assert_eq!(
+            format!("{}", number),
+            "99,999,999",
+            "Should drop the decimal places if they don't all fit."
+        );
+
+        let mut number = Number::from(1_234_567.891);
+        number.set_display_mode(DisplayMode::Fix(2));
+        assert_eq!(format!("{}", number), "1,234,567.89");
+
+        let mut number = Number::from(9_999_999.996);
+        number.set_display_mode(DisplayMode::Fix(2));
+        assert_eq!(
+            format!("{}", number),
+            "10,000,000",
+            "Should drop the decimal places when rounding adds an integer digit."
+        );
+    }
+
+    #[test]
+    fn formats_editing() {
+        let mut number = Number::from(0);
+        number.set_display_mode(DisplayMode::Fix(2));
+        number.append(1);
+        assert_eq!(
+            format!("{}", number),
+            "1",
+            "Should format a number being edited as entered."
+        );
+
+        number.set_editing(false);
+        assert_eq!(format!("{}", number), "1.00");
+    }
+
+    #[test]
+    fn formats_sci() {
+        let mut number = Number::from(12345.);
+        number.set_display_mode(DisplayMode::Sci(2));
+        assert_eq!(format!("{}", number), "1.23e4");
+    }
+
+    #[test]
+    fn formats_sci_small() {
+        let mut number = Number::from(-0.00123);
+        number.set_display_mode(DisplayMode::Sci(1));
+        assert_eq!(format!("{}", number), "-1.2e-3");
+    }
+
+    #[test]
+    fn formats_sci_budget() {
+        let mut number = Number::from(1. / 3.);
+        number.set_display_mode(DisplayMode::Sci(12));
+        assert_eq!(
+            format!("{}", number),
+            "3.3333333e-1",
+            "Should limit decimal places to the number of significant figures."
+        );
+    }
+
+    #[test]
+    fn formats_eng() {
+        let mut number = Number::from(12345.);
+        number.set_display_mode(DisplayMode::Eng(1));
+        assert_eq!(format!("{}", number), "12.3e3");
+    }
+
+    #[test]
+    fn formats_eng_small() {
+        let mut number = Number::from(0.00045);
+        number.set_display_mode(DisplayMode::Eng(0));
+        assert_eq!(format!("{}", number), "450e-6");
+    }
+
+    #[test]
+    fn formats_eng_carry() {
+        let mut number = Number::from(999_960.);
+        number.set_display_mode(DisplayMode::Eng(1));
+        assert_eq!(
+            format!("{}", number),
+            "1.0e6",
+            "Should carry rounding over to the next exponent."
+        );
+    }
+
+    #[test]
+    fn append_limit() {
+        let mut number = Number::from(0);
+        number.set_sf(Some(3));
+        number.decimalise();
+        for _ in 0..3 {
+            number.append(1);
+        }
+        assert_eq!(
+            number.to_string(),
+            "0.11",
+            "Should not append beyond the number of significant figures."
+        );
+    }
+
+    #[test]
+    fn append_limit_negative() {
+        let mut number = Number::from(-1);
+        number.set_sf(Some(3));
+        number.append(2);
+        number.append(3);
+        number.append(4);
+        assert_eq!(
+            number.to_string(),
+            "-123",
+            "Should only count digits towards the limit."
+        );
+    }
+
+    #[test]
+    fn formats_sf() {
+        let mut number = Number::from(1234567890123.);
+        number.set_sf(Some(16));
+        assert_eq!(format!("{}", number), "1,234,567,890,123");
+    }
+
+    #[test]
+    fn formats_integer() {
+        let grouped = |digits: &str| {
+            let mut formatted = String::new();
+            super::write_grouped(&mut formatted, &digits, None).unwrap();
+            formatted
+        };
+        assert_eq!(grouped("0"), "0");
+        assert_eq!(grouped("999"), "999");
+        assert_eq!(grouped("1000"), "1,000");
+        assert_eq!(grouped("123456789"), "123,456,789");
+        assert_eq!(
+            grouped("12345678901234567890"),
+            "12,345,678,901,234,567,890"
+        );
+    }
+
+    #[test]
+    fn formats_beyond_integer_range() {
+        let mut number = Number::from(1e20);
+        number.set_sf(None);
+        assert_eq!(
+            format!("{}", number),
+            "100,000,000,000,000,000,000",
+            "Should format the digits of the value, rather than casting it."
+        );
+
+        let mut number = Number::from(-1e20);
+        number.set_sf(None);
+        assert_eq!(format!("{}", number), "-100,000,000,000,000,000,000");
+    }
+
+    #[test]
+    fn append_exponent() {
+        let mut number = Number::from(6);
+        number.decimalise();
+        number.append(0);
+        number.append(2);
+        number.append(2);
+        number.start_exponent();
+        assert_eq!(number.to_string(), "6.022e0");
+
+        number.append(2);
+        number.append(3);
+        assert_eq!(
+            number.value, 6.022e23,
+            "Should append number to the exponent."
+        );
+        assert_eq!(number.to_string(), "6.022e23");
+    }
+
+    #[test]
+    fn negate_exponent() {
+        let mut number = Number::from(5);
+        number.start_exponent();
+        number.negate_exponent();
+        number.append(3);
+        assert_eq!(number.value, 0.005);
+        assert_eq!(number.to_string(), "5e-3");
+
+        number.negate_exponent();
+        assert_eq!(number.value, 5000.);
+    }
+
+    #[test]
+    fn append_exponent_limit() {
+        let mut number = Number::from(1);
+        number.start_exponent();
+        for _ in 0..4 {
+            number.append(1);
+        }
+        assert_eq!(number.to_string(), "1e111");
+    }
+
+    #[test]
+    fn append_to_error() {
+        let mut number = Number::from(f64::NEG_INFINITY);
+        number.append(5);
+        assert_eq!(
+            number.to_string(),
+            "5",
+            "Should start a new number when appending to an error."
+        );
+    }
+
+    #[test]
+    fn start_exponent_on_error() {
+        let mut number = Number::from(f64::NAN);
+        number.start_exponent();
+        number.append(2);
+        assert_eq!(
+            number.to_string(),
+            "0e2",
+            "Should start a new number when entering the exponent of an error."
+        );
+    }
+
+    #[test]
+    fn decimalise_exponent() {
+        let mut number = Number::from(1);
+        number.start_exponent();
+        number.append(2);
+        number.decimalise();
+        assert_eq!(
+            number.to_string(),
+            "1e2",
+            "Should not decimalise the exponent."
+        );
+    }
+
+    #[test]
+    fn set_value_exponent() {
+        let mut number = Number::from(1);
+        number.start_exponent();
+        number.append(2);
+        number.set_value(-100.);
+        assert!(!number.entering_exponent());
+        assert_eq!(number.to_string(), "-100");
+    }
 }
@@ -1,15 +1,121 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{complex::Complex, registry::OperatorRegistry};
+use alloc::string::String;
+
+/// The associativity of an operator, which determines how operators of the
+/// same order are grouped.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Associativity {
+    /// Grouped from the left, `a - b - c` is `(a - b) - c`.
+    Left,
+    /// Grouped from the right, `a ^ b ^ c` is `a ^ (b ^ c)`.
+    Right,
+}
+
 /// Represents a mathematical operator.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct Operator {
     /// The character identifier of the operator.
     pub id: char,
+    /// The symbol used to display the operator.
+    pub glyph: String,
     /// The operator's mathematical function.
     pub function: fn(f64, f64) -> f64,
+    /// The operator's mathematical function applied to complex values, if it
+    /// has one.
+    pub complex_function: Option<fn(Complex, Complex) -> Complex>,
     /// The BIDMAS order of operation.
     order: usize,
+    /// The grouping of operators with the same order of operation.
+    associativity: Associativity,
+    /// Whether a percentage entered after the operator is a percentage of the
+    /// calculation before it, as for addition and subtraction.
+    additive: bool,
 }
 
 impl Operator {
+    /// Create a new operator.
+    ///
+    /// The order is the operator's place in the BIDMAS order of operations,
+    /// where operators with a lower order are resolved first. The built-in
+    /// operators have the orders:
+    /// 1. Powers, roots and logarithms.
+    /// 2. Division and multiplication.
+    /// 3. Addition and subtraction.
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::{Associativity, Operator};
+    ///
+    /// let modulo = Operator::new('m', "mod", 2, Associativity::Left, |x, y| x.rem_euclid(y));
+    ///
+    /// assert!(modulo.precedes(&Operator::add()), "Modulo comes before addition.");
+    /// ```
+    pub fn new(
+        id: char,
+        glyph: &str,
+        order: usize,
+        associativity: Associativity,
+        function: fn(f64, f64) -> f64,
+    ) -> Self {
+        Self {
+            id,
+            glyph: String::from(glyph),
+            function,
+            complex_function: None,
+            order,
+            associativity,
+            additive: false,
+        }
+    }
+
+    /// Sets the operator's mathematical function applied to complex values.
+    pub fn with_complex_function(
+        mut self,
+        complex_function: fn(Complex, Complex) -> Complex,
+    ) -> Self {
+        self.complex_function = Some(complex_function);
+        self
+    }
+
+    /// Sets whether a percentage entered after the operator is a percentage of
+    /// the calculation before it, as for addition and subtraction. For
+    /// example, `50 + 10 %` becomes `50 + 5`, while `50 × 10 %` becomes
+    /// `50 × 0.1`.
+    pub fn with_additive(mut self, additive: bool) -> Self {
+        self.additive = additive;
+        self
+    }
+
+    /// Checks if a percentage entered after the operator is a percentage of
+    /// the calculation before it, see [`Operator::with_additive`].
+    pub fn is_additive(&self) -> bool {
+        self.additive
+    }
+
+    /// Returns the BIDMAS order of operation.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns the grouping of operators with the same order of operation.
+    pub fn associativity(&self) -> Associativity {
+        self.associativity
+    }
+
+    /// Applies the operator to two complex values.
+    ///
+    /// If the operator has no complex function, the result is only defined
+    /// for real values.
+    pub fn apply_complex(&self, x: Complex, y: Complex) -> Complex {
+        match self.complex_function {
+            Some(complex_function) => complex_function(x, y),
+            None if x.is_real() && y.is_real() => Complex::from((self.function)(x.re, y.re)),
+            None => Complex::from(f64::NAN),
+        }
+    }
+
     /// Checks if the operator come after another in the BIDMAS order of operations.
     ///
     /// Example
@@ -25,12 +131,95 @@ impl Operator {
         self.order > other.order
     }
 
+    /// Checks if the operator should be resolved before a following operator.
+    ///
+    /// This is the case if the operator comes first in the BIDMAS order of
+    /// operations, or if both have the same order and the following operator
+    /// is left associative.
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::Operator;
+    ///
+    /// let subtract = Operator::subtract();
+    /// let power = Operator::power();
+    ///
+    /// assert!(subtract.precedes(&Operator::add()), "Subtraction groups from the left.");
+    /// assert!(!power.precedes(&Operator::power()), "Powers group from the right.");
+    /// ```
+    pub fn precedes(&self, next: &Self) -> bool {
+        match self.order.cmp(&next.order) {
+            core::cmp::Ordering::Less => true,
+            core::cmp::Ordering::Equal => next.associativity == Associativity::Left,
+            core::cmp::Ordering::Greater => false,
+        }
+    }
+
+    /// Power operator, `x` raised to the power of `y`.
+    pub fn power() -> Self {
+        Self {
+            id: '^',
+            glyph: String::from("^"),
+            function: |x, y| x.powf(y),
+            complex_function: Some(|x, y| x.powc(y)),
+            order: 1,
+            associativity: Associativity::Right,
+            additive: false,
+        }
+    }
+
+    /// Root operator, the `y`th root of `x`.
+    pub fn root() -> Self {
+        Self {
+            id: '√',
+            glyph: String::from("ʸ√"),
+            function: |x, y| {
+                // Odd roots of negative numbers are real.
+                if x < 0. && y.fract() == 0. && y % 2. != 0. {
+                    -(-x).powf(1. / y)
+                } else {
+                    x.powf(1. / y)
+                }
+            },
+            complex_function: Some(|x, y| x.powc(y.recip())),
+            order: 1,
+            associativity: Associativity::Left,
+            additive: false,
+        }
+    }
+
+    /// Logarithm operator, the logarithm of `x` to the base `y`.
+    pub fn logarithm() -> Self {
+        Self {
+            id: '㏒',
+            glyph: String::from("logᵧ"),
+            function: |x, y| {
+                // Common bases have more accurate implementations.
+                if y == 10. {
+                    x.log10()
+                } else if y == 2. {
+                    x.log2()
+                } else {
+                    x.ln() / y.ln()
+                }
+            },
+            complex_function: Some(|x, y| x.ln() / y.ln()),
+            order: 1,
+            associativity: Associativity::Left,
+            additive: false,
+        }
+    }
+
     /// Division operator.
     pub fn divide() -> Self {
         Self {
             id: '/',
+            glyph: String::from("÷"),
             function: |x, y| x / y,
+            complex_function: Some(|x, y| x / y),
             order: 2,
+            associativity: Associativity::Left,
+            additive: false,
         }
     }
 
@@ -38,8 +227,12 @@ impl Operator {
     pub fn multiply() -> Self {
         Self {
             id: '*',
+            glyph: String::from("×"),
             function: |x, y| x * y,
+            complex_function: Some(|x, y| x * y),
             order: 2,
+            associativity: Associativity::Left,
+            additive: false,
         }
     }
 
@@ -47,8 +240,12 @@ impl Operator {
     pub fn add() -> Self {
         Self {
             id: '+',
+            glyph: String::from("+"),
             function: |x, y| x + y,
+            complex_function: Some(|x, y| x + y),
             order: 3,
+            associativity: Associativity::Left,
+            additive: true,
         }
     }
 
@@ -56,17 +253,34 @@ impl Operator {
     pub fn subtract() -> Self {
         Self {
             id: '-',
+            glyph: String::from("−"),
             function: |x, y| x - y,
+            complex_function: Some(|x, y| x - y),
             order: 3,
+            associativity: Associativity::Left,
+            additive: true,
         }
     }
 }
 
+impl PartialEq for Operator {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.order == other.order
+    }
+}
+
 impl TryFrom<&str> for Operator {
     type Error = &'static str;
 
     /// Create an operator from its string identifier.
     ///
+    /// Resolves built-in operators through the default [`OperatorRegistry`],
+    /// so operators registered on a calculator aren't found and a replaced
+    /// built-in operator resolves to the original. Use
+    /// [`Calculator::operator`] to resolve through a calculator's registry.
+    ///
+    /// [`Calculator::operator`]: crate::Calculator::operator
+    ///
     /// Example
     /// ```rust
     /// use ios_calculator::Operator;
@@ -75,21 +289,22 @@ impl TryFrom<&str> for Operator {
     /// let divide = Operator::try_from("/").unwrap();
     /// let add = Operator::try_from("+").unwrap();
     /// let subtract = Operator::try_from("-").unwrap();
+    /// let power = Operator::try_from("^").unwrap();
+    /// let root = Operator::try_from("√").unwrap();
+    /// let logarithm = Operator::try_from("㏒").unwrap();
     /// ```
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "*" => Ok(Operator::multiply()),
-            "/" => Ok(Operator::divide()),
-            "+" => Ok(Operator::add()),
-            "-" => Ok(Operator::subtract()),
-            _ => Err("Unknown operator."),
-        }
+        OperatorRegistry::default()
+            .get(value)
+            .cloned()
+            .ok_or("Unknown operator.")
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Operator;
+    use super::{Associativity, Operator};
+    use crate::complex::Complex;
 
     #[test]
     fn after_true() {
@@ -109,4 +324,47 @@ mod tests {
             "Multiplication does not come after addition."
         );
     }
+
+    #[test]
+    fn precedes_same_order() {
+        assert!(Operator::subtract().precedes(&Operator::add()));
+        assert!(Operator::divide().precedes(&Operator::multiply()));
+    }
+
+    #[test]
+    fn precedes_right_associative() {
+        assert!(!Operator::power().precedes(&Operator::power()));
+        assert!(Operator::power().precedes(&Operator::multiply()));
+    }
+
+    #[test]
+    fn precedes_higher_order() {
+        assert!(!Operator::add().precedes(&Operator::power()));
+        assert!(!Operator::add().precedes(&Operator::multiply()));
+    }
+
+    #[test]
+    fn root_negative() {
+        assert_eq!((Operator::root().function)(-8., 3.), -2.);
+        assert!((Operator::root().function)(-4., 2.).is_nan());
+    }
+
+    #[test]
+    fn logarithm() {
+        assert_eq!((Operator::logarithm().function)(8., 2.), 3.);
+    }
+
+    #[test]
+    fn apply_complex_fallback() {
+        let modulo = Operator::new('m', "mod", 2, Associativity::Left, |x, y| x % y);
+        assert_eq!(
+            modulo.apply_complex(Complex::from(7.), Complex::from(4.)),
+            Complex::from(3.),
+            "Should use the real function for real values."
+        );
+        assert!(modulo
+            .apply_complex(Complex::I, Complex::from(4.))
+            .re
+            .is_nan());
+    }
 }
@@ -0,0 +1,84 @@
+/// A seedable pseudo-random number generator.
+///
+/// Uses the SplitMix64 algorithm, which is small and fast but not
+/// cryptographically secure.
+#[derive(Debug, Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Create a new random number generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Random { state: seed }
+    }
+
+    /// Returns the next random 64 bit integer.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns the next random number in the range `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        // Use the 53 most significant bits, the precision of a f64.
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+impl Default for Random {
+    /// Create a new random number generator seeded from entropy, or from zero
+    /// without the `std` feature.
+    fn default() -> Self {
+        Random::new(entropy())
+    }
+}
+
+/// Returns a seed that differs between generators.
+#[cfg(feature = "std")]
+fn entropy() -> u64 {
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+    };
+
+    // Each `RandomState` is keyed differently, so hashing nothing differs.
+    RandomState::new().build_hasher().finish()
+}
+
+/// Returns a fixed seed, as there is no source of entropy.
+#[cfg(not(feature = "std"))]
+fn entropy() -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Random;
+
+    #[test]
+    fn deterministic() {
+        let mut a = Random::new(42);
+        let mut b = Random::new(42);
+        assert_eq!(
+            a.next_f64(),
+            b.next_f64(),
+            "Should be deterministic for a seed."
+        );
+    }
+
+    #[test]
+    fn range() {
+        let mut random = Random::new(7);
+        for _ in 0..1000 {
+            let value = random.next_f64();
+            assert!(
+                (0. ..1.).contains(&value),
+                "Should be between zero and one."
+            );
+        }
+    }
+}
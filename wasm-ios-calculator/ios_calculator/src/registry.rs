@@ -0,0 +1,129 @@
+use crate::{error::Error, key::Key, operator::Operator};
+use alloc::collections::BTreeMap;
+
+/// A collection of operators, identified by their character identifier.
+///
+/// The default registry contains the built-in operators.
+#[derive(Debug, Clone)]
+pub struct OperatorRegistry {
+    operators: BTreeMap<char, Operator>,
+}
+
+impl OperatorRegistry {
+    /// Create a registry with no operators.
+    pub fn empty() -> Self {
+        OperatorRegistry {
+            operators: BTreeMap::new(),
+        }
+    }
+
+    /// Registers an operator.
+    ///
+    /// Returns the operator previously registered with the same identifier,
+    /// if one exists. Fails if the identifier is reserved for another key,
+    /// such as `.` or `=`, as operators are resolved before other keys.
+    pub fn register(&mut self, operator: Operator) -> Result<Option<Operator>, Error> {
+        let mut id = [0; 4];
+        if Key::from_id(operator.id.encode_utf8(&mut id), &OperatorRegistry::empty()).is_ok() {
+            return Err(Error::ReservedOperator(operator.id));
+        }
+        Ok(self.operators.insert(operator.id, operator))
+    }
+
+    /// Removes an operator, returning it if it was registered.
+    pub fn unregister(&mut self, id: char) -> Option<Operator> {
+        self.operators.remove(&id)
+    }
+
+    /// Returns the operator with a string identifier if it is registered.
+    ///
+    /// Example
+    /// ```rust
+    /// use ios_calculator::{Operator, OperatorRegistry};
+    ///
+    /// let registry = OperatorRegistry::default();
+    ///
+    /// assert_eq!(registry.get("+"), Some(&Operator::add()));
+    /// assert_eq!(registry.get("++"), None);
+    /// ```
+    pub fn get(&self, id: &str) -> Option<&Operator> {
+        let mut chars = id.chars();
+        match (chars.next(), chars.next()) {
+            (Some(id), None) => self.operators.get(&id),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the registered operators.
+    pub fn iter(&self) -> impl Iterator<Item = &Operator> {
+        self.operators.values()
+    }
+}
+
+impl Default for OperatorRegistry {
+    fn default() -> Self {
+        let mut registry = OperatorRegistry::empty();
+        for operator in [
+            Operator::add(),
+            Operator::subtract(),
+            Operator::multiply(),
+            Operator::divide(),
+            Operator::power(),
+            Operator::root(),
+            Operator::logarithm(),
+        ] {
+            registry.operators.insert(operator.id, operator);
+        }
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OperatorRegistry;
+    use crate::{
+        error::Error,
+        operator::{Associativity, Operator},
+    };
+    use alloc::string::ToString;
+
+    #[test]
+    fn register() {
+        let mut registry = OperatorRegistry::default();
+        let modulo = Operator::new('m', "mod", 2, Associativity::Left, |x, y| x % y);
+
+        assert_eq!(registry.register(modulo.clone()), Ok(None));
+        assert_eq!(registry.get("m"), Some(&modulo));
+    }
+
+    #[test]
+    fn register_replaces() {
+        let mut registry = OperatorRegistry::default();
+        let add = Operator::new('+', "plus", 3, Associativity::Left, |x, y| x + y);
+
+        assert_eq!(registry.register(add), Ok(Some(Operator::add())));
+        assert_eq!(registry.get("+").unwrap().glyph, "plus");
+    }
+
+    #[test]
+    fn register_reserved() {
+        let mut registry = OperatorRegistry::default();
+        for id in ['.', '=', 'e', 'i', '%', '7'] {
+            let operator = Operator::new(id, "?", 2, Associativity::Left, |x, y| x * y);
+            assert_eq!(
+                registry.register(operator),
+                Err(Error::ReservedOperator(id)),
+                "Should not replace the {} key.",
+                id
+            );
+            assert_eq!(registry.get(&id.to_string()), None);
+        }
+    }
+
+    #[test]
+    fn unregister() {
+        let mut registry = OperatorRegistry::default();
+        registry.unregister('+');
+        assert_eq!(registry.get("+"), None);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::operator::Operator;
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Words for the numbers below twenty.
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+/// Words for the multiples of ten, starting from twenty.
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// Words for each power of a thousand, starting from a thousand.
+const SCALES: [&str; 6] = [
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+/// Speaks a number below a thousand in English.
+fn hundreds(value: u64) -> String {
+    let mut words = Vec::new();
+    if value >= 100 {
+        words.push(format!("{} hundred", ONES[(value / 100) as usize]));
+    }
+
+    match value % 100 {
+        0 if value >= 100 => {}
+        rest @ 0..=19 => words.push(ONES[rest as usize].to_string()),
+        rest => {
+            let tens = TENS[(rest / 10 - 2) as usize];
+            match rest % 10 {
+                0 => words.push(tens.to_string()),
+                ones => words.push(format!("{}-{}", tens, ONES[ones as usize])),
+            }
+        }
+    }
+    words.join(" ")
+}
+
+/// Speaks a whole number in English.
+///
+/// For example `1200` is spoken as "one thousand two hundred".
+pub fn cardinal(value: u64) -> String {
+    if value < 1000 {
+        return hundreds(value);
+    }
+
+    let mut words = Vec::new();
+    let mut rest = value;
+    let mut scale = 0;
+    while rest > 0 {
+        let group = rest % 1000;
+        if group > 0 {
+            match scale {
+                0 => words.push(hundreds(group)),
+                _ => words.push(format!("{} {}", hundreds(group), SCALES[scale - 1])),
+            }
+        }
+        rest /= 1000;
+        scale += 1;
+    }
+    words.reverse();
+    words.join(" ")
+}
+
+/// Speaks a whole number as an English ordinal, for example "twenty-first".
+pub fn ordinal(value: u64) -> String {
+    let words = cardinal(value);
+    let split = words.rfind([' ', '-']).map_or(0, |index| index + 1);
+    let (start, last) = words.split_at(split);
+
+    let last = match last {
+        "one" => String::from("first"),
+        "two" => String::from("second"),
+        "three" => String::from("third"),
+        "five" => String::from("fifth"),
+        "eight" => String::from("eighth"),
+        "nine" => String::from("ninth"),
+        "twelve" => String::from("twelfth"),
+        _ if last.ends_with('y') => format!("{}ieth", &last[..last.len() - 1]),
+        _ => format!("{}th", last),
+    };
+    format!("{}{}", start, last)
+}
+
+/// Speaks a run of digits, which may contain thousands separators.
+fn digits(text: &str) -> String {
+    let value: String = text.chars().filter(char::is_ascii_digit).collect();
+    match value.parse() {
+        Ok(value) => cardinal(value),
+        // Too long to speak as a whole number.
+        Err(_) => value
+            .chars()
+            .map(|digit| ONES[digit.to_digit(10).unwrap_or(0) as usize])
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Speaks the text shown on the display in English.
+///
+/// Example
+/// ```rust
+/// use ios_calculator::speak;
+///
+/// assert_eq!(speak("1,200.5"), "one thousand two hundred point five");
+/// assert_eq!(speak("1.2e9"), "one point two times ten to the ninth");
+/// ```
+pub fn speak(text: &str) -> String {
+    if text == "Error" {
+        return String::from("error");
+    }
+
+    let mut words: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '0'..='9' => {
+                // Whole part.
+                let mut whole = String::from(char);
+                while let Some(next) = chars.next_if(|c| c.is_ascii_digit() || *c == ',') {
+                    whole.push(next);
+                }
+                words.push(digits(&whole));
+
+                // Fractional part, spoken digit by digit.
+                if chars.next_if_eq(&'.').is_some() {
+                    words.push(String::from("point"));
+                    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                        words.push(digits(&digit.to_string()));
+                    }
+                }
+            }
+            '.' => words.push(String::from("point")),
+            'e' => {
+                let negative = chars.next_if_eq(&'-').is_some();
+                let mut exponent = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    exponent.push(digit);
+                }
+
+                words.push(String::from("times ten to the"));
+                if negative {
+                    words.push(String::from("minus"));
+                }
+                match exponent.parse() {
+                    Ok(exponent) => words.push(ordinal(exponent)),
+                    // The exponent has not been entered yet.
+                    Err(_) => words.push(String::from("power")),
+                }
+            }
+            '-' => words.push(String::from("minus")),
+            '+' => words.push(String::from("plus")),
+            'i' => words.push(String::from("i")),
+            '∠' => words.push(String::from("at an angle of")),
+            '°' => words.push(String::from("degrees")),
+            _ => words.push(char.to_string()),
+        }
+    }
+    words.join(" ")
+}
+
+/// Speaks an [`Operator`] in English, falling back to its glyph.
+pub fn speak_operator(operator: &Operator) -> String {
+    let words = match operator.id {
+        '+' => "plus",
+        '-' => "minus",
+        '*' => "times",
+        '/' => "divided by",
+        '^' => "to the power of",
+        '√' => "root of",
+        '㏒' => "log base",
+        _ => return operator.glyph.to_owned(),
+    };
+    String::from(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cardinals() {
+        assert_eq!(cardinal(0), "zero");
+        assert_eq!(cardinal(21), "twenty-one");
+        assert_eq!(cardinal(100), "one hundred");
+        assert_eq!(cardinal(1_000_005), "one million five");
+        assert_eq!(cardinal(1234), "one thousand two hundred thirty-four");
+    }
+
+    #[test]
+    fn ordinals() {
+        assert_eq!(ordinal(9), "ninth");
+        assert_eq!(ordinal(12), "twelfth");
+        assert_eq!(ordinal(20), "twentieth");
+        assert_eq!(ordinal(21), "twenty-first");
+        assert_eq!(ordinal(100), "one hundredth");
+    }
+
+    #[test]
+    fn speak_decimal() {
+        assert_eq!(speak("1."), "one point");
+        assert_eq!(speak("-0.05"), "minus zero point zero five");
+    }
+
+    #[test]
+    fn speak_exponent() {
+        assert_eq!(
+            speak("1.5e-12"),
+            "one point five times ten to the minus twelfth"
+        );
+        assert_eq!(
+            speak("2e"),
+            "two times ten to the power",
+            "Should speak an exponent that is being entered."
+        );
+    }
+
+    #[test]
+    fn speak_complex() {
+        assert_eq!(speak("2+3i"), "two plus three i");
+        assert_eq!(speak("2∠90°"), "two at an angle of ninety degrees");
+    }
+
+    #[test]
+    fn speak_error() {
+        assert_eq!(speak("Error"), "error");
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{complex::Complex, engine::Token, number::Number, operator::Operator};
+use alloc::{string::String, vec::Vec};
+use arrayvec::ArrayVec;
+use core::{
+    fmt::{self, Debug, Write},
+    ops::{Deref, DerefMut, Range},
+};
+
+/// The text of a [`Number`] as it is entered.
+///
+/// Implemented by [`String`], and by [`InlineText`](crate::InlineText) for
+/// numbers that are stored inline.
+pub trait Text: Write + Deref<Target = str> + Default + Clone + PartialEq + Debug {
+    /// Shortens the text to a length in bytes.
+    fn truncate(&mut self, len: usize);
+
+    /// Inserts a character at a byte index, failing if there is no room.
+    fn insert(&mut self, index: usize, c: char) -> fmt::Result;
+
+    /// Removes the character at a byte index.
+    fn remove(&mut self, index: usize) -> char;
+}
+
+impl Text for String {
+    fn truncate(&mut self, len: usize) {
+        String::truncate(self, len);
+    }
+
+    fn insert(&mut self, index: usize, c: char) -> fmt::Result {
+        String::insert(self, index, c);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> char {
+        String::remove(self, index)
+    }
+}
+
+/// A stack of items, such as the tokens in a calculator's buffer.
+pub(crate) trait Stack<T>: Default + Deref<Target = [T]> + DerefMut {
+    /// Checks if there is room to push a number of items.
+    fn has_room(&self, additional: usize) -> bool;
+
+    /// Pushes an item onto the stack.
+    ///
+    /// Panics if there is no room, see [`Stack::has_room`].
+    fn push(&mut self, item: T);
+
+    /// Pops the last item off the stack.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Shortens the stack to a number of items.
+    fn truncate(&mut self, len: usize);
+
+    /// Removes a range of items.
+    fn remove_range(&mut self, range: Range<usize>);
+}
+
+impl<T> Stack<T> for Vec<T> {
+    fn has_room(&self, _additional: usize) -> bool {
+        true
+    }
+
+    fn push(&mut self, item: T) {
+        Vec::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Vec::pop(self)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len);
+    }
+
+    fn remove_range(&mut self, range: Range<usize>) {
+        self.drain(range);
+    }
+}
+
+impl<T, const N: usize> Stack<T> for ArrayVec<T, N> {
+    fn has_room(&self, additional: usize) -> bool {
+        self.remaining_capacity() >= additional
+    }
+
+    fn push(&mut self, item: T) {
+        ArrayVec::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        ArrayVec::pop(self)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        ArrayVec::truncate(self, len);
+    }
+
+    fn remove_range(&mut self, range: Range<usize>) {
+        self.drain(range);
+    }
+}
+
+/// An operator that can be entered into a calculator's buffer.
+pub(crate) trait Operation: Clone + PartialEq + Debug {
+    /// Converts an [`Operator`] for storage in the buffer.
+    fn from_operator(operator: &Operator) -> Self;
+
+    /// Returns the character identifier of the operator.
+    fn id(&self) -> char;
+
+    /// Applies the operator to two real values.
+    fn apply(&self, x: f64, y: f64) -> f64;
+
+    /// Applies the operator to two complex values, see
+    /// [`Operator::apply_complex`].
+    fn apply_complex(&self, x: Complex, y: Complex) -> Complex;
+
+    /// Checks if the operator should be resolved before a following operator,
+    /// see [`Operator::precedes`].
+    fn precedes(&self, next: &Self) -> bool;
+
+    /// Checks if a percentage entered after the operator is a percentage of
+    /// the calculation before it, see [`Operator::is_additive`].
+    fn is_additive(&self) -> bool;
+}
+
+impl Operation for Operator {
+    fn from_operator(operator: &Operator) -> Self {
+        operator.clone()
+    }
+
+    fn id(&self) -> char {
+        self.id
+    }
+
+    fn apply(&self, x: f64, y: f64) -> f64 {
+        (self.function)(x, y)
+    }
+
+    fn apply_complex(&self, x: Complex, y: Complex) -> Complex {
+        Operator::apply_complex(self, x, y)
+    }
+
+    fn precedes(&self, next: &Self) -> bool {
+        Operator::precedes(self, next)
+    }
+
+    fn is_additive(&self) -> bool {
+        Operator::is_additive(self)
+    }
+}
+
+/// How a calculator stores its numbers, operators and buffer.
+///
+/// [`Heap`] allocates as needed, while the storage of a
+/// [`FixedCalculator`](crate::FixedCalculator) is inline so that pressing
+/// keys doesn't allocate.
+pub(crate) trait Storage: Sized + Clone + PartialEq + Debug {
+    /// The text of a number as it is entered.
+    type Text: Text;
+
+    /// An operator in the buffer.
+    type Operator: Operation;
+
+    /// The buffer of tokens.
+    type Tokens: Stack<Token<Self>> + Clone + Debug;
+
+    /// A stack used while evaluating the buffer.
+    type Stack<T>: Stack<T>;
+}
+
+/// Storage that allocates as needed, used by [`Calculator`](crate::Calculator).
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Heap;
+
+impl Storage for Heap {
+    type Text = String;
+    type Operator = Operator;
+    type Tokens = Vec<Token<Heap>>;
+    type Stack<T> = Vec<T>;
+}
+
+/// The [`Number`] stored by a calculator.
+pub(crate) type StoredNumber<S> = Number<<S as Storage>::Text>;
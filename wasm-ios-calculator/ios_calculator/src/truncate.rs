@@ -13,7 +13,7 @@ impl Truncate for str {
 
         for (index, char) in self.chars().enumerate() {
             slice_index = index;
-            if char.is_digit(10) {
+            if char.is_ascii_digit() {
                 numeric_char_count += 1;
             }
             if numeric_char_count == max_count {
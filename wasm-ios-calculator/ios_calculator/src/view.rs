@@ -0,0 +1,26 @@
+use crate::angle::AngleMode;
+use alloc::string::String;
+
+/// A snapshot of everything a user interface needs to render the calculator.
+///
+/// Two view models compare equal when nothing visible has changed, so a
+/// front end can skip redrawing.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ViewModel {
+    /// The text shown on the display.
+    pub display: String,
+    /// The calculation entered so far, using operator glyphs.
+    pub expression: String,
+    /// The expression as it would be read aloud in English.
+    pub spoken: String,
+    /// The id of the operator that is highlighted, if any.
+    pub active_operator: Option<char>,
+    /// Should the clear key read 'AC' rather than 'C'?
+    pub all_clear: bool,
+    /// Is the last operation remembered for repeated equals?
+    pub memory: bool,
+    /// Is the display showing an error?
+    pub error: bool,
+    /// The unit used for angles.
+    pub angle_mode: AngleMode,
+}
@@ -0,0 +1,84 @@
+//! Checks that pressing keys on a [`FixedCalculator`], and formatting its
+//! display, doesn't allocate.
+use arrayvec::ArrayString;
+use ios_calculator::{parse_keys, DisplayMode, FixedCalculator, OperatorRegistry};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Write,
+};
+
+thread_local! {
+    /// The number of allocations made by the current thread.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The system allocator, counting allocations.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Presses keys, formatting the display after each key, and returns the
+/// number of allocations made.
+fn count_allocations(calc: &mut FixedCalculator, keys: &str) -> usize {
+    let keys = parse_keys(keys, &OperatorRegistry::default()).unwrap();
+
+    let before = ALLOCATIONS.with(Cell::get);
+    for key in keys.iter() {
+        calc.press(key);
+        let mut display = ArrayString::<64>::new();
+        write!(display, "{}", calc.display()).unwrap();
+    }
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn press_without_allocating() {
+    let mut calc: FixedCalculator = FixedCalculator::new();
+    assert_eq!(
+        count_allocations(
+            &mut calc,
+            "12.5*3-4^2=={EE}±3+{²√}50%=π*{Rand}/0={AC}{AC}99999999*99999999*99999999*99999999=±",
+        ),
+        0,
+        "Should press keys without allocating."
+    );
+    assert_eq!(calc.display().to_string(), "-9.99999e31");
+}
+
+#[test]
+fn format_without_allocating() {
+    let mut calc: FixedCalculator = FixedCalculator::new();
+    calc.set_complex_mode(true);
+    assert_eq!(
+        count_allocations(&mut calc, "2/3=+i=∠{Rad}∠1{EE}±20*i="),
+        0,
+        "Should format complex numbers without allocating."
+    );
+
+    for display_mode in [
+        DisplayMode::Fix(2),
+        DisplayMode::Sci(3),
+        DisplayMode::Eng(1),
+    ] {
+        calc.set_display_mode(display_mode);
+        assert_eq!(
+            count_allocations(&mut calc, "{AC}{AC}1/7=*12345=9999999.999="),
+            0,
+            "Should format in {:?} without allocating.",
+            display_mode
+        );
+    }
+}
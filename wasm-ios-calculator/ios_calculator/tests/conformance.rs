@@ -0,0 +1,222 @@
+//! Checks the calculators against the fixtures in `tests/conformance`.
+//!
+//! The fixtures are regression snapshots: their displays were recorded from
+//! this crate, so they catch changes in behavior rather than prove that it
+//! matches the iOS calculator.
+//!
+//! Each fixture is a text file of cases. A case starts with its name in
+//! brackets, followed by a line for each key pressed: the key's id, see
+//! [`Key::from_id`], and the display expected after it is pressed, separated
+//! by whitespace.
+//!
+//! ```text
+//! # Lines starting with '#' are comments.
+//! [add]
+//! 1    1
+//! +    1
+//! 2    2
+//! =    3
+//! ```
+//!
+//! A case is pressed on a new [`Calculator`] and a new [`FixedCalculator`]
+//! with the default capacity. Its display width can be set with `@width 15`
+//! before its first key.
+//!
+//! Every mismatch is reported, rather than just the first.
+use ios_calculator::{Calculator, FixedCalculator, Key};
+use std::{fs, path::Path};
+
+/// A key press and the display expected after it.
+struct Step {
+    line: usize,
+    key: String,
+    expected: String,
+}
+
+/// A sequence of keys pressed on a new calculator.
+struct Case {
+    name: String,
+    display_width: Option<u8>,
+    steps: Vec<Step>,
+}
+
+/// Parses the cases in a fixture.
+///
+/// Returns the lines that couldn't be parsed as errors.
+fn parse(fixture: &str) -> Result<Vec<Case>, Vec<String>> {
+    let mut cases: Vec<Case> = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in fixture.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            cases.push(Case {
+                name: name.to_string(),
+                display_width: None,
+                steps: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some(case) = cases.last_mut() else {
+            errors.push(format!("{}: Expected a case name.", line_number));
+            continue;
+        };
+
+        if let Some(width) = line.strip_prefix("@width") {
+            match width.trim().parse() {
+                Ok(width) if case.steps.is_empty() => case.display_width = Some(width),
+                Ok(..) => errors.push(format!(
+                    "{}: The width must be set before the first key.",
+                    line_number
+                )),
+                Err(..) => errors.push(format!("{}: Invalid width.", line_number)),
+            }
+            continue;
+        }
+
+        match line.split_once(char::is_whitespace) {
+            Some((key, expected)) => case.steps.push(Step {
+                line: line_number,
+                key: key.to_string(),
+                expected: expected.trim().to_string(),
+            }),
+            None => errors.push(format!(
+                "{}: Expected a key and the display after it.",
+                line_number
+            )),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(cases)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Runs the cases in a fixture, returning a message for each mismatch.
+fn run(name: &str, fixture: &str) -> Vec<String> {
+    let cases = match parse(fixture) {
+        Ok(cases) => cases,
+        Err(errors) => {
+            return errors
+                .into_iter()
+                .map(|error| format!("{}:{}", name, error))
+                .collect()
+        }
+    };
+
+    let mut mismatches = Vec::new();
+    for case in cases {
+        let mut calc = Calculator::new();
+        let mut fixed: FixedCalculator = FixedCalculator::new();
+        if let Some(width) = case.display_width {
+            calc.set_display_width(width);
+            fixed.set_display_width(width);
+        }
+
+        for step in case.steps {
+            let key = match Key::from_id(&step.key, calc.operators()) {
+                Ok(key) => key,
+                Err(error) => {
+                    mismatches.push(format!("{}:{}: [{}] {}", name, step.line, case.name, error));
+                    continue;
+                }
+            };
+            calc.press(&key);
+            fixed.press(&key);
+
+            let display = calc.display().to_string();
+            if display != step.expected {
+                mismatches.push(format!(
+                    "{}:{}: [{}] Expected \"{}\" after {}, but found \"{}\".",
+                    name, step.line, case.name, step.expected, step.key, display
+                ));
+            }
+            let display = fixed.display().to_string();
+            if display != step.expected {
+                mismatches.push(format!(
+                    "{}:{}: [{}] Expected \"{}\" after {} on FixedCalculator, but found \"{}\".",
+                    name, step.line, case.name, step.expected, step.key, display
+                ));
+            }
+        }
+    }
+    mismatches
+}
+
+#[test]
+fn conformance() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/conformance");
+    let mut paths: Vec<_> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    paths.sort();
+    assert!(
+        !paths.is_empty(),
+        "Should find fixtures in {:?}.",
+        directory
+    );
+
+    let mismatches: Vec<String> = paths
+        .iter()
+        .flat_map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            run(&name, &fs::read_to_string(path).unwrap())
+        })
+        .collect();
+    assert!(
+        mismatches.is_empty(),
+        "Should match every fixture, found {} mismatches:\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}
+
+#[test]
+fn reports_every_mismatch() {
+    let fixture = "\
+[add]
+1    1
++    2
+2    2
+=    4
+
+[unknown]
+?    0
+
+[landscape]
+@width 15
+1    1
+";
+    assert_eq!(
+        run("test.txt", fixture),
+        vec![
+            "test.txt:3: [add] Expected \"2\" after +, but found \"1\".",
+            "test.txt:3: [add] Expected \"2\" after + on FixedCalculator, but found \"1\".",
+            "test.txt:5: [add] Expected \"4\" after =, but found \"3\".",
+            "test.txt:5: [add] Expected \"4\" after = on FixedCalculator, but found \"3\".",
+            "test.txt:8: [unknown] Unknown key \"?\".",
+        ]
+    );
+
+    assert_eq!(
+        run("test.txt", "1    1\n[add]\n1\n"),
+        vec![
+            "test.txt:1: Expected a case name.",
+            "test.txt:3: Expected a key and the display after it.",
+        ],
+        "Should report every line that can't be parsed."
+    );
+}
@@ -0,0 +1,15 @@
+use std::{env, path::PathBuf};
+
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    // Generate the C header from the exported functions. The committed copy
+    // in `include` is checked against it by the `header` test.
+    cbindgen::generate(&crate_dir)
+        .expect("Unable to generate the C header.")
+        .write_to_file(out_dir.join("ios_calculator.h"));
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,104 @@
+#ifndef IOS_CALCULATOR_H
+#define IOS_CALCULATOR_H
+
+/* Generated by cbindgen from ios_calculator_ffi. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The result of a call into the library.
+ */
+typedef enum IosCalculatorStatus {
+  /**
+   * The call succeeded.
+   */
+  IOS_CALCULATOR_STATUS_OK = 0,
+  /**
+   * A required pointer was null.
+   */
+  IOS_CALCULATOR_STATUS_NULL_POINTER = 1,
+  /**
+   * A string was not valid UTF-8.
+   */
+  IOS_CALCULATOR_STATUS_INVALID_UTF8 = 2,
+  /**
+   * The key id was not recognised.
+   */
+  IOS_CALCULATOR_STATUS_UNKNOWN_KEY = 3,
+  /**
+   * The buffer is too small for the output.
+   */
+  IOS_CALCULATOR_STATUS_BUFFER_TOO_SMALL = 4,
+  /**
+   * The library panicked. The handle should no longer be used.
+   */
+  IOS_CALCULATOR_STATUS_PANIC = 5,
+  /**
+   * The calculator reported another error.
+   */
+  IOS_CALCULATOR_STATUS_ERROR = 6,
+} IosCalculatorStatus;
+
+/**
+ * An opaque handle to a calculator.
+ */
+typedef struct IosCalculator IosCalculator;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a new calculator, returning null on failure.
+ *
+ * The calculator must be destroyed with [`ios_calculator_free`].
+ */
+struct IosCalculator *ios_calculator_new(void);
+
+/**
+ * Destroys a calculator. Passing null does nothing.
+ *
+ * # Safety
+ *
+ * `calculator` must be null or a pointer returned by [`ios_calculator_new`]
+ * that has not already been destroyed.
+ */
+void ios_calculator_free(struct IosCalculator *calculator);
+
+/**
+ * Presses the key with the given id, such as `"7"`, `"+"` or `"AC"`.
+ *
+ * # Safety
+ *
+ * `calculator` must be a live pointer returned by [`ios_calculator_new`] and
+ * `id` a NUL-terminated string.
+ */
+enum IosCalculatorStatus ios_calculator_press(struct IosCalculator *calculator, const char *id);
+
+/**
+ * Writes the display, as a NUL-terminated UTF-8 string, into a buffer of
+ * `length` bytes.
+ *
+ * If `required` is not null, the buffer size needed for the display
+ * (including the NUL) is written to it, even when the buffer is too small.
+ *
+ * # Safety
+ *
+ * `calculator` must be a live pointer returned by [`ios_calculator_new`],
+ * and `buffer` must be valid for `length` bytes. `buffer` may be null when
+ * `length` is zero.
+ */
+enum IosCalculatorStatus ios_calculator_display(const struct IosCalculator *calculator,
+                                                char *buffer,
+                                                size_t length,
+                                                size_t *required);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* IOS_CALCULATOR_H */
@@ -0,0 +1,216 @@
+#![warn(missing_docs)]
+//! # iOS Calculator FFI
+//!
+//! A C interface to [`ios_calculator`], for linking the engine from C and C++.
+//!
+//! The calculator is exposed as an opaque [`IosCalculator`] handle. Every
+//! function reports failure with an [`IosCalculatorStatus`], and panics are
+//! caught before they can cross the boundary.
+//!
+//! The header `include/ios_calculator.h` is generated by the build script.
+use ios_calculator::{Calculator, Error};
+use std::{
+    ffi::{c_char, CStr},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr, slice,
+};
+
+/// An opaque handle to a calculator.
+pub struct IosCalculator {
+    calculator: Calculator,
+}
+
+/// The result of a call into the library.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IosCalculatorStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// A string was not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// The key id was not recognised.
+    UnknownKey = 3,
+    /// The buffer is too small for the output.
+    BufferTooSmall = 4,
+    /// The library panicked. The handle should no longer be used.
+    Panic = 5,
+    /// The calculator reported another error.
+    Error = 6,
+}
+
+impl From<Error> for IosCalculatorStatus {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::UnknownKey(..) => IosCalculatorStatus::UnknownKey,
+            _ => IosCalculatorStatus::Error,
+        }
+    }
+}
+
+/// Runs a function, converting a panic into [`IosCalculatorStatus::Panic`].
+fn guard<F: FnOnce() -> IosCalculatorStatus>(function: F) -> IosCalculatorStatus {
+    catch_unwind(AssertUnwindSafe(function)).unwrap_or(IosCalculatorStatus::Panic)
+}
+
+/// Creates a new calculator, returning null on failure.
+///
+/// The calculator must be destroyed with [`ios_calculator_free`].
+#[no_mangle]
+pub extern "C" fn ios_calculator_new() -> *mut IosCalculator {
+    catch_unwind(|| {
+        Box::into_raw(Box::new(IosCalculator {
+            calculator: Calculator::new(),
+        }))
+    })
+    .unwrap_or(ptr::null_mut())
+}
+
+/// Destroys a calculator. Passing null does nothing.
+///
+/// # Safety
+///
+/// `calculator` must be null or a pointer returned by [`ios_calculator_new`]
+/// that has not already been destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn ios_calculator_free(calculator: *mut IosCalculator) {
+    if !calculator.is_null() {
+        let _ = catch_unwind(|| drop(Box::from_raw(calculator)));
+    }
+}
+
+/// Presses the key with the given id, such as `"7"`, `"+"` or `"AC"`.
+///
+/// # Safety
+///
+/// `calculator` must be a live pointer returned by [`ios_calculator_new`] and
+/// `id` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ios_calculator_press(
+    calculator: *mut IosCalculator,
+    id: *const c_char,
+) -> IosCalculatorStatus {
+    if calculator.is_null() || id.is_null() {
+        return IosCalculatorStatus::NullPointer;
+    }
+
+    guard(|| {
+        let calculator = &mut (*calculator).calculator;
+        let Ok(id) = CStr::from_ptr(id).to_str() else {
+            return IosCalculatorStatus::InvalidUtf8;
+        };
+
+        match calculator.key(id) {
+            Ok(key) => {
+                calculator.press(&key);
+                IosCalculatorStatus::Ok
+            }
+            Err(error) => error.into(),
+        }
+    })
+}
+
+/// Writes the display, as a NUL-terminated UTF-8 string, into a buffer of
+/// `length` bytes.
+///
+/// If `required` is not null, the buffer size needed for the display
+/// (including the NUL) is written to it, even when the buffer is too small.
+///
+/// # Safety
+///
+/// `calculator` must be a live pointer returned by [`ios_calculator_new`],
+/// and `buffer` must be valid for `length` bytes. `buffer` may be null when
+/// `length` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn ios_calculator_display(
+    calculator: *const IosCalculator,
+    buffer: *mut c_char,
+    length: usize,
+    required: *mut usize,
+) -> IosCalculatorStatus {
+    if calculator.is_null() || (buffer.is_null() && length > 0) {
+        return IosCalculatorStatus::NullPointer;
+    }
+
+    guard(|| {
+        let display = (*calculator).calculator.display().to_string();
+        let bytes = display.as_bytes();
+
+        if !required.is_null() {
+            *required = bytes.len() + 1;
+        }
+        if bytes.len() + 1 > length {
+            return IosCalculatorStatus::BufferTooSmall;
+        }
+
+        let buffer = slice::from_raw_parts_mut(buffer as *mut u8, length);
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        buffer[bytes.len()] = 0;
+        IosCalculatorStatus::Ok
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    /// Reads the display of a calculator into a string.
+    fn display(calculator: *const IosCalculator) -> String {
+        let mut buffer = [0 as c_char; 32];
+        let status =
+            unsafe { ios_calculator_display(calculator, buffer.as_mut_ptr(), 32, ptr::null_mut()) };
+        assert_eq!(status, IosCalculatorStatus::Ok);
+        unsafe { CStr::from_ptr(buffer.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn press(calculator: *mut IosCalculator, id: &str) -> IosCalculatorStatus {
+        let id = CString::new(id).unwrap();
+        unsafe { ios_calculator_press(calculator, id.as_ptr()) }
+    }
+
+    #[test]
+    fn calculate() {
+        let calculator = ios_calculator_new();
+        for id in ["1", "2", "*", "3", "="] {
+            assert_eq!(press(calculator, id), IosCalculatorStatus::Ok);
+        }
+        assert_eq!(display(calculator), "36");
+        unsafe { ios_calculator_free(calculator) };
+    }
+
+    #[test]
+    fn unknown_key() {
+        let calculator = ios_calculator_new();
+        assert_eq!(press(calculator, "?"), IosCalculatorStatus::UnknownKey);
+        unsafe { ios_calculator_free(calculator) };
+    }
+
+    #[test]
+    fn null_pointers() {
+        assert_eq!(
+            press(ptr::null_mut(), "1"),
+            IosCalculatorStatus::NullPointer
+        );
+        unsafe { ios_calculator_free(ptr::null_mut()) };
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let calculator = ios_calculator_new();
+        for id in ["1", "2", "3", "4"] {
+            press(calculator, id);
+        }
+
+        let mut buffer = [0 as c_char; 4];
+        let mut required = 0;
+        let status =
+            unsafe { ios_calculator_display(calculator, buffer.as_mut_ptr(), 4, &mut required) };
+        assert_eq!(status, IosCalculatorStatus::BufferTooSmall);
+        assert_eq!(required, "1,234".len() + 1);
+        unsafe { ios_calculator_free(calculator) };
+    }
+}
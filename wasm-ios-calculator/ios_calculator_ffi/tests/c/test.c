@@ -0,0 +1,39 @@
+/* Exercises the C interface. Exits with a non-zero status on failure. */
+#include <stdio.h>
+#include <string.h>
+
+#include "ios_calculator.h"
+
+#define CHECK(condition)                                              \
+  if (!(condition)) {                                                 \
+    fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+            #condition);                                              \
+    return 1;                                                         \
+  }
+
+int main(void) {
+  IosCalculator *calculator = ios_calculator_new();
+  CHECK(calculator != NULL);
+
+  const char *keys[] = {"1", "2", "+", "3", "0", "="};
+  for (size_t i = 0; i < sizeof(keys) / sizeof(keys[0]); i++) {
+    CHECK(ios_calculator_press(calculator, keys[i]) == IOS_CALCULATOR_STATUS_OK);
+  }
+
+  char display[16];
+  size_t required = 0;
+  CHECK(ios_calculator_display(calculator, display, sizeof(display), &required) ==
+        IOS_CALCULATOR_STATUS_OK);
+  CHECK(strcmp(display, "42") == 0);
+  CHECK(required == 3);
+
+  /* Errors are reported as status codes. */
+  CHECK(ios_calculator_press(calculator, "?") == IOS_CALCULATOR_STATUS_UNKNOWN_KEY);
+  CHECK(ios_calculator_press(NULL, "1") == IOS_CALCULATOR_STATUS_NULL_POINTER);
+  CHECK(ios_calculator_display(calculator, display, 2, &required) ==
+        IOS_CALCULATOR_STATUS_BUFFER_TOO_SMALL);
+
+  ios_calculator_free(calculator);
+  puts("ok");
+  return 0;
+}
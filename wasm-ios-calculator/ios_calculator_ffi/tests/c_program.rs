@@ -0,0 +1,81 @@
+//! Compiles and runs the C test program against the static library.
+use std::{env, path::PathBuf, process::Command};
+
+/// The static library, as named on the target.
+#[cfg(not(target_env = "msvc"))]
+const STATIC_LIB: &str = "libios_calculator_ffi.a";
+#[cfg(target_env = "msvc")]
+const STATIC_LIB: &str = "ios_calculator_ffi.lib";
+
+/// The native libraries that the static library must be linked with, as
+/// printed by `rustc --print native-static-libs` for the target.
+#[cfg(target_os = "linux")]
+const NATIVE_STATIC_LIBS: &[&str] = &[
+    "-lgcc_s",
+    "-lutil",
+    "-lrt",
+    "-lpthread",
+    "-lm",
+    "-ldl",
+    "-lc",
+];
+#[cfg(target_os = "macos")]
+const NATIVE_STATIC_LIBS: &[&str] = &["-liconv", "-lSystem", "-lc", "-lm"];
+#[cfg(all(windows, target_env = "gnu"))]
+const NATIVE_STATIC_LIBS: &[&str] = &[
+    "-lkernel32",
+    "-ladvapi32",
+    "-lntdll",
+    "-luserenv",
+    "-lws2_32",
+    "-ldbghelp",
+];
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "macos",
+    all(windows, target_env = "gnu")
+)))]
+const NATIVE_STATIC_LIBS: &[&str] = &["-lpthread", "-lm"];
+
+#[test]
+#[cfg_attr(
+    target_env = "msvc",
+    ignore = "MSVC doesn't accept the compiler arguments used by the test."
+)]
+fn c_program() {
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    assert!(
+        Command::new(&compiler).arg("--version").output().is_ok(),
+        "Should find a C compiler to build the test program, set CC to choose one."
+    );
+
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Integration tests are built into `target/<profile>/deps`, beside which
+    // the library is built.
+    let target_dir = env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(|deps| deps.parent())
+        .unwrap()
+        .to_path_buf();
+    let executable = target_dir.join("ios_calculator_c_test");
+
+    let status = Command::new(&compiler)
+        .arg(manifest_dir.join("tests/c/test.c"))
+        .arg("-I")
+        .arg(env!("OUT_DIR"))
+        .arg(target_dir.join(STATIC_LIB))
+        .args(NATIVE_STATIC_LIBS)
+        .arg("-o")
+        .arg(&executable)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Should compile the C test program.");
+
+    let output = Command::new(&executable).output().unwrap();
+    assert!(
+        output.status.success(),
+        "Should pass the C test program: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
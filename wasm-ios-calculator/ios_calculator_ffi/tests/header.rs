@@ -0,0 +1,21 @@
+//! Checks that the committed C header matches the exported functions.
+//!
+//! Run with `UPDATE_HEADER=1` to copy the generated header into `include`.
+use std::{env, fs, path::PathBuf};
+
+/// The header generated by the build script.
+const GENERATED: &str = include_str!(concat!(env!("OUT_DIR"), "/ios_calculator.h"));
+
+#[test]
+fn header() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("include/ios_calculator.h");
+    if env::var_os("UPDATE_HEADER").is_some() {
+        fs::write(&path, GENERATED).unwrap();
+    }
+
+    assert!(
+        fs::read_to_string(&path).unwrap() == GENERATED,
+        "Should match the generated header, update it with \
+         `UPDATE_HEADER=1 cargo test -p ios_calculator_ffi --test header`."
+    );
+}
@@ -0,0 +1,156 @@
+//! # iOS Calculator for Python
+//!
+//! An optional Python extension module wrapping [`ios_calculator`].
+//!
+//! ```python
+//! import ios_calculator
+//!
+//! calc = ios_calculator.Calculator()
+//! calc.press_keys("1+2=")
+//! assert calc.display == "3"
+//! ```
+use ios_calculator::{Calculator as Engine, Error, Number};
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+
+create_exception!(
+    ios_calculator,
+    CalculatorError,
+    PyException,
+    "Raised when the calculator reports an error."
+);
+create_exception!(
+    ios_calculator,
+    UnknownKeyError,
+    CalculatorError,
+    "Raised when a key id is not recognised."
+);
+create_exception!(
+    ios_calculator,
+    MacroError,
+    CalculatorError,
+    "Raised when recording or playing a macro fails."
+);
+
+/// Converts a calculator [`Error`] into the matching Python exception.
+fn py_err(error: Error) -> PyErr {
+    let message = error.to_string();
+    match error {
+        Error::UnknownKey(..) => UnknownKeyError::new_err(message),
+        Error::NotRecording | Error::UnknownMacro(..) => MacroError::new_err(message),
+        _ => CalculatorError::new_err(message),
+    }
+}
+
+/// A simple iOS calculator.
+#[pyclass(name = "Calculator", module = "ios_calculator")]
+#[derive(Default)]
+pub struct Calculator {
+    calculator: Engine,
+}
+
+#[pymethods]
+impl Calculator {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Presses the key with the given id, such as "7", "+" or "AC".
+    fn press(&mut self, id: &str) -> PyResult<()> {
+        let key = self.calculator.key(id).map_err(py_err)?;
+        self.calculator.press(&key);
+        Ok(())
+    }
+
+    /// Presses a sequence of keys, such as "1+2=" or "{AC}".
+    fn press_keys(&mut self, keys: &str) -> PyResult<()> {
+        for key in self.calculator.parse_keys(keys).map_err(py_err)? {
+            self.calculator.press(&key);
+        }
+        Ok(())
+    }
+
+    /// Presses a sequence of keys, returning the display after each one.
+    fn replay(&mut self, keys: &str) -> PyResult<Vec<String>> {
+        let keys = self.calculator.parse_keys(keys).map_err(py_err)?;
+        Ok(self.calculator.replay(&keys))
+    }
+
+    /// Performs the clear operation.
+    fn clear(&mut self) {
+        self.calculator.clear();
+    }
+
+    /// The text shown on the display.
+    #[getter]
+    fn display(&self) -> String {
+        self.calculator.display().to_string()
+    }
+
+    /// The value shown on the display.
+    #[getter]
+    fn value(&self) -> f64 {
+        self.calculator.display().value()
+    }
+
+    /// The calculation entered so far.
+    #[getter]
+    fn expression(&self) -> String {
+        self.calculator.expression()
+    }
+
+    /// The id of the active operator, or None.
+    #[getter]
+    fn active_operator(&self) -> Option<char> {
+        self.calculator
+            .active_operator()
+            .map(|operator| operator.id)
+    }
+
+    /// True if the clear key reads 'AC'.
+    #[getter]
+    fn all_clear(&self) -> bool {
+        self.calculator.cleared()
+    }
+
+    /// Starts recording a macro.
+    fn start_recording(&mut self) {
+        self.calculator.start_recording();
+    }
+
+    /// Saves the macro being recorded under a name.
+    fn stop_recording(&mut self, name: &str) -> PyResult<()> {
+        self.calculator.stop_recording(name).map_err(py_err)
+    }
+
+    /// Plays a saved macro.
+    fn play_macro(&mut self, name: &str) -> PyResult<()> {
+        self.calculator.play_macro(name).map_err(py_err)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Calculator(display={:?})", self.display())
+    }
+}
+
+/// Formats a value as the calculator would display it.
+#[pyfunction]
+#[pyo3(signature = (value, significant_figures = 9))]
+fn format_number(value: f64, significant_figures: u8) -> String {
+    let mut number = Number::from(value);
+    number.set_sf(Some(significant_figures));
+    number.to_string()
+}
+
+/// The `ios_calculator` Python module.
+#[pymodule]
+#[pyo3(name = "ios_calculator")]
+fn python_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    module.add_class::<Calculator>()?;
+    module.add_function(wrap_pyfunction!(format_number, module)?)?;
+    module.add("CalculatorError", py.get_type::<CalculatorError>())?;
+    module.add("UnknownKeyError", py.get_type::<UnknownKeyError>())?;
+    module.add("MacroError", py.get_type::<MacroError>())?;
+    Ok(())
+}
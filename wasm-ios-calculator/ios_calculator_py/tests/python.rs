@@ -0,0 +1,58 @@
+//! Runs the Python tests against the built extension module.
+use std::{
+    env::{self, consts},
+    fs,
+    path::PathBuf,
+    process::Command,
+};
+
+/// The file name that Python imports the extension module from.
+#[cfg(windows)]
+const MODULE: &str = "ios_calculator.pyd";
+#[cfg(not(windows))]
+const MODULE: &str = "ios_calculator.so";
+
+#[test]
+fn python_tests() {
+    let python = env::var("PYO3_PYTHON").unwrap_or_else(|_| String::from("python3"));
+    assert!(
+        Command::new(&python).arg("--version").output().is_ok(),
+        "Should find a Python interpreter to run the tests, set PYO3_PYTHON to choose one."
+    );
+
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Integration tests are built into `target/<profile>/deps`, beside which
+    // the library is built.
+    let target_dir = env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(|deps| deps.parent())
+        .unwrap()
+        .to_path_buf();
+
+    // Python imports the module by its name, not the library's file name,
+    // such as `libios_calculator_py.so` or `ios_calculator_py.dll`.
+    let module_dir = target_dir.join("python");
+    fs::create_dir_all(&module_dir).unwrap();
+    fs::copy(
+        target_dir.join(format!(
+            "{}ios_calculator_py{}",
+            consts::DLL_PREFIX,
+            consts::DLL_SUFFIX
+        )),
+        module_dir.join(MODULE),
+    )
+    .unwrap();
+
+    let output = Command::new(&python)
+        .args(["-m", "unittest", "discover", "-s"])
+        .arg(manifest_dir.join("tests"))
+        .env("PYTHONPATH", &module_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "Should pass the Python tests: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,104 @@
+"""A reference implementation of the calculator's arithmetic, written
+independently of the Rust engine.
+
+Supports digits (up to nine per number), the four basic operators and
+equals, including operator precedence, replacing the active operator and
+repeated equals.
+"""
+
+import math
+
+# The most digits that can be entered into a number.
+MAX_DIGITS = 9
+
+OPERATORS = {
+    "+": (3, lambda a, b: a + b),
+    "-": (3, lambda a, b: a - b),
+    "*": (2, lambda a, b: a * b),
+    "/": (2, lambda a, b: divide(a, b)),
+}
+
+
+def divide(a, b):
+    """Divides as IEEE 754 does, rather than raising on division by zero."""
+    if b == 0:
+        if a == 0 or math.isnan(a):
+            return math.nan
+        return math.copysign(math.inf, a) * math.copysign(1, b)
+    return a / b
+
+
+class ReferenceCalculator:
+    def __init__(self):
+        # Alternating numbers and operators, starting with a number.
+        self.tokens = [0.0]
+        # The operator and operand repeated by equals.
+        self.memory = None
+        # The digits of the number being entered, if any.
+        self.entry = None
+
+    @property
+    def value(self):
+        """The value on the display."""
+        for token in reversed(self.tokens):
+            if not isinstance(token, str):
+                return token
+
+    def reduce(self):
+        """Replaces the last operation with its result."""
+        a, operator, b = self.tokens[-3:]
+        del self.tokens[-3:]
+        self.tokens.append(OPERATORS[operator][1](a, b))
+
+    def press(self, key):
+        if key.isdigit():
+            self.press_digit(key)
+        elif key in OPERATORS:
+            self.press_operator(key)
+        elif key == "=":
+            self.press_equals()
+        else:
+            raise ValueError(f"Unsupported key {key!r}.")
+
+    def press_digit(self, digit):
+        if self.entry is not None:
+            if len(self.entry) >= MAX_DIGITS:
+                return
+            self.entry = digit if self.entry == "0" else self.entry + digit
+            self.tokens[-1] = float(self.entry)
+        elif isinstance(self.tokens[-1], str):
+            self.entry = digit
+            self.tokens.append(float(digit))
+        else:
+            # Replace the displayed result, keeping the memory.
+            self.entry = digit
+            self.tokens[-1] = float(digit)
+
+    def press_operator(self, operator):
+        self.entry = None
+        if isinstance(self.tokens[-1], str):
+            self.tokens.pop()
+            self.press_operator(operator)
+            return
+
+        if len(self.tokens) == 1:
+            self.memory = None
+        order = OPERATORS[operator][0]
+        while len(self.tokens) >= 3 and OPERATORS[self.tokens[-2]][0] <= order:
+            self.reduce()
+        self.tokens.append(operator)
+
+    def press_equals(self):
+        self.entry = None
+        if isinstance(self.tokens[-1], str):
+            return
+
+        if len(self.tokens) == 1:
+            if self.memory is not None:
+                operator, b = self.memory
+                self.tokens = [OPERATORS[operator][1](self.tokens[0], b)]
+            return
+
+        self.memory = (self.tokens[-2], self.tokens[-1])
+        while len(self.tokens) >= 3:
+            self.reduce()
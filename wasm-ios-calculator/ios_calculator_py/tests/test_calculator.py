@@ -0,0 +1,91 @@
+"""Tests for the Python bindings, including a randomized cross-check against
+the reference implementation.
+
+Run with the built extension module on the Python path:
+
+    python3 -m unittest discover ios_calculator_py/tests
+"""
+
+import random
+import unittest
+
+import ios_calculator
+from reference import ReferenceCalculator
+
+
+def random_number(rng):
+    return str(rng.choice([0, rng.randint(1, 9), rng.randint(10, 999), rng.randint(1000, 999999)]))
+
+
+def random_keys(rng, length):
+    """Generates a sequence of keys the reference implementation supports."""
+    keys = list(random_number(rng))
+    while len(keys) < length:
+        choice = rng.random()
+        if choice < 0.6:
+            keys.append(rng.choice("+-*/"))
+            if rng.random() < 0.1:
+                # Replace the operator.
+                keys.append(rng.choice("+-*/"))
+            keys.extend(random_number(rng))
+        elif choice < 0.9:
+            keys.append("=")
+        else:
+            keys.extend(random_number(rng))
+    return keys
+
+
+class TestBindings(unittest.TestCase):
+    def test_press_keys(self):
+        calc = ios_calculator.Calculator()
+        calc.press_keys("12*3=")
+        self.assertEqual(calc.display, "36")
+        self.assertEqual(calc.value, 36.0)
+
+    def test_active_operator(self):
+        calc = ios_calculator.Calculator()
+        self.assertTrue(calc.all_clear)
+        calc.press("5")
+        calc.press("+")
+        self.assertEqual(calc.active_operator, "+")
+        self.assertFalse(calc.all_clear)
+
+    def test_unknown_key(self):
+        calc = ios_calculator.Calculator()
+        with self.assertRaises(ios_calculator.UnknownKeyError):
+            calc.press("?")
+        with self.assertRaises(ios_calculator.CalculatorError):
+            calc.press_keys("{unterminated")
+
+    def test_macro_error(self):
+        calc = ios_calculator.Calculator()
+        with self.assertRaises(ios_calculator.MacroError):
+            calc.play_macro("missing")
+
+    def test_format_number(self):
+        self.assertEqual(ios_calculator.format_number(1234.5), "1,234.5")
+        self.assertEqual(ios_calculator.format_number(1 / 3, 4), "0.333")
+
+
+class TestCrossCheck(unittest.TestCase):
+    def test_random_sequences(self):
+        rng = random.Random(0)
+        for _ in range(200):
+            keys = random_keys(rng, 200)
+            calc = ios_calculator.Calculator()
+            reference = ReferenceCalculator()
+
+            for index, key in enumerate(keys):
+                calc.press(key)
+                reference.press(key)
+
+                expected = ios_calculator.format_number(reference.value)
+                if calc.display != expected:
+                    self.fail(
+                        f"Display {calc.display!r} should be {expected!r} after "
+                        f"{''.join(keys[: index + 1])!r}."
+                    )
+
+
+if __name__ == "__main__":
+    unittest.main()
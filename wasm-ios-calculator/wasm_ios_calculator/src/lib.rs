@@ -1,35 +1,175 @@
-use ios_calculator::{Calculator, Operator};
+use ios_calculator::{AngleMode, Calculator, DisplayMode, ViewModel};
 
 use std::str;
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
     // Use `js_namespace` here to bind `console.log(..)` instead of just
     // `log(..)`
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
+
+    #[wasm_bindgen(js_name = queueMicrotask)]
+    fn queue_microtask(callback: &js_sys::Function);
+}
+
+// Imported functions panic outside the browser, such as when fuzzing.
+#[cfg(not(target_arch = "wasm32"))]
+fn log(s: &str) {
+    eprintln!("{}", s);
+}
+
+/// A snapshot of everything the user interface needs to render.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub struct CalculatorState {
+    view: ViewModel,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+impl CalculatorState {
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn display(&self) -> String {
+        self.view.display.to_owned()
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn expression(&self) -> String {
+        self.view.expression.to_owned()
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=activeOperator))]
+    pub fn active_operator(&self) -> Option<char> {
+        self.view.active_operator
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=showAllClear))]
+    pub fn show_all_clear(&self) -> bool {
+        self.view.all_clear
+    }
+
+    /// The expression as it would be read aloud by a screen reader.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=ariaLabel))]
+    pub fn aria_label(&self) -> String {
+        self.view.spoken.to_owned()
+    }
+
+    /// The label of the clear key, `AC` or `C`.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=clearLabel))]
+    pub fn clear_label(&self) -> String {
+        String::from(if self.view.all_clear { "AC" } else { "C" })
+    }
+
+    /// The unit used for angles, `degrees` or `radians`.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=angleMode))]
+    pub fn angle_mode(&self) -> String {
+        String::from(match self.view.angle_mode {
+            AngleMode::Degrees => "degrees",
+            AngleMode::Radians => "radians",
+        })
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn memory(&self) -> bool {
+        self.view.memory
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn error(&self) -> bool {
+        self.view.error
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub struct WasmIosCalculator {
     calculator: Calculator,
+    /// Callbacks invoked when the state changes.
+    subscribers: Vec<js_sys::Function>,
+    /// The view last sent to subscribers.
+    view: ViewModel,
+}
+
+impl Default for WasmIosCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 impl WasmIosCalculator {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen(constructor))]
     pub fn new() -> Self {
+        #[allow(unused_mut)]
+        let mut calculator = Calculator::new();
+
+        // Seed random numbers differently for each page load.
+        #[cfg(target_arch = "wasm32")]
+        calculator.seed_random((js_sys::Math::random() * u64::MAX as f64) as u64);
+
         WasmIosCalculator {
-            calculator: Calculator::new(),
+            view: calculator.view(),
+            calculator,
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Returns a consistent snapshot of the calculator's state.
+    pub fn state(&self) -> CalculatorState {
+        CalculatorState {
+            view: self.calculator.view(),
         }
     }
 
+    /// Registers a callback that is passed a `CalculatorState` whenever the
+    /// state changes.
+    pub fn subscribe(&mut self, callback: js_sys::Function) {
+        self.subscribers.push(callback);
+    }
+
+    /// Removes a callback registered with `subscribe`.
+    pub fn unsubscribe(&mut self, callback: &js_sys::Function) {
+        self.subscribers.retain(|subscriber| subscriber != callback);
+    }
+
+    /// Updates the view last sent to subscribers, returning whether it
+    /// changed.
+    fn update_view(&mut self) -> bool {
+        let view = self.calculator.view();
+        if view == self.view {
+            return false;
+        }
+        self.view = view;
+        true
+    }
+
+    /// Invokes the subscribed callbacks once if the view has changed.
+    ///
+    /// The callbacks are called in a microtask, after the calculator has been
+    /// released, so that they can read it.
+    fn notify(&mut self) {
+        if self.update_view() {
+            #[cfg(target_arch = "wasm32")]
+            for subscriber in &self.subscribers {
+                let state = CalculatorState {
+                    view: self.view.clone(),
+                };
+                queue_microtask(&subscriber.bind1(&JsValue::NULL, &state.into()));
+            }
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name=seedRandom))]
+    pub fn seed_random(&mut self, seed: u64) {
+        self.calculator.seed_random(seed);
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=activeOperator))]
     pub fn active_operator(&self) -> Option<char> {
         self.calculator
             .active_operator()
-            .and_then(|operator| Some(operator.id.to_owned()))
+            .map(|operator| operator.id.to_owned())
     }
 
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=showAllClear))]
@@ -37,42 +177,94 @@ impl WasmIosCalculator {
         self.calculator.cleared()
     }
 
-    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
-    pub fn output(&mut self) -> String {
-        self.calculator.output().to_string()
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=complexMode))]
+    pub fn complex_mode(&self) -> bool {
+        self.calculator.complex_mode()
     }
 
-    #[cfg_attr(target_arch="wasm32", wasm_bindgen(js_name=buttonPressed))]
-    pub fn button_pressed(&mut self, id: &str) {
-        // If a number was pressed.
-        if let Ok(number) = id.parse::<u8>() {
-            self.calculator.submit_number(number);
-            return;
-        }
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(setter, js_name=complexMode))]
+    pub fn set_complex_mode(&mut self, complex: bool) {
+        self.calculator.set_complex_mode(complex);
+        self.notify();
+    }
 
-        // If an operator was pressed.
-        if let Ok(operator) = Operator::try_from(id) {
-            self.calculator.submit_operator(operator);
-        }
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=displayWidth))]
+    pub fn display_width(&self) -> u8 {
+        self.calculator.display_width()
+    }
 
-        match id {
-            "." => {
-                self.calculator.submit_decimal();
-            }
-            "=" => {
-                self.calculator.submit_equals();
-            }
-            "±" => {
-                self.calculator.submit_negative();
-            }
-            "%" => {
-                self.calculator.submit_percentage();
-            }
-            "c" => {
-                self.calculator.clear();
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(setter, js_name=displayWidth))]
+    pub fn set_display_width(&mut self, display_width: u8) {
+        self.calculator.set_display_width(display_width);
+        self.notify();
+    }
+
+    /// Sets the display mode, one of `normal`, `fix`, `sci` or `eng`,
+    /// with the given number of decimal places.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name=setDisplayMode))]
+    pub fn set_display_mode(&mut self, mode: &str, decimals: u8) {
+        let display_mode = match mode {
+            "normal" => DisplayMode::Normal,
+            "fix" => DisplayMode::Fix(decimals),
+            "sci" => DisplayMode::Sci(decimals),
+            "eng" => DisplayMode::Eng(decimals),
+            _ => {
+                log("Unknown display mode.");
+                return;
             }
-            _ => log("Unknown button pressed."),
+        };
+        self.calculator.set_display_mode(display_mode);
+        self.notify();
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn output(&self) -> String {
+        self.calculator.display().to_string()
+    }
+
+    /// The expression as it would be read aloud by a screen reader.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter, js_name=ariaLabel))]
+    pub fn aria_label(&self) -> String {
+        self.calculator.spoken_expression()
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name=recordMacro))]
+    pub fn record_macro(&mut self) {
+        self.calculator.start_recording();
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn recording(&self) -> bool {
+        self.calculator.recording()
+    }
+
+    /// Saves the macro being recorded, returning false if nothing was being
+    /// recorded.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name=stopRecording))]
+    pub fn stop_recording(&mut self, name: &str) -> bool {
+        self.calculator.stop_recording(name).is_ok()
+    }
+
+    /// Plays a saved macro, returning false if it doesn't exist.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name=playMacro))]
+    pub fn play_macro(&mut self, name: &str) -> bool {
+        let played = self.calculator.play_macro(name).is_ok();
+        self.notify();
+        played
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen(getter))]
+    pub fn macros(&self) -> Vec<String> {
+        self.calculator.macros().map(String::from).collect()
+    }
+
+    #[cfg_attr(target_arch="wasm32", wasm_bindgen(js_name=buttonPressed))]
+    pub fn button_pressed(&mut self, id: &str) {
+        match self.calculator.key(id) {
+            Ok(key) => self.calculator.press(&key),
+            Err(_) => log("Unknown button pressed."),
         }
+        self.notify();
     }
 }
 
@@ -96,6 +288,108 @@ mod test {
         };
     }
 
+    #[test]
+    fn tracks_view() {
+        let mut calc = WasmIosCalculator::new();
+        calc.button_pressed("3");
+        calc.button_pressed("+");
+        assert_eq!(calc.view.display, "3");
+        assert_eq!(calc.view.active_operator, Some('+'));
+
+        calc.set_display_width(4);
+        calc.button_pressed("1");
+        calc.button_pressed("2");
+        calc.button_pressed("3");
+        calc.button_pressed("4");
+        calc.button_pressed("5");
+        assert_eq!(calc.view.display, "1,234");
+    }
+
+    #[test]
+    fn update_view() {
+        let mut calc = WasmIosCalculator::new();
+        assert!(!calc.update_view(), "Should start with the current view.");
+
+        for (id, changed) in [
+            ("7", true),
+            ("+", true),
+            ("+", false),
+            ("2", true),
+            ("=", true),
+        ] {
+            calc.calculator.press(&calc.calculator.key(id).unwrap());
+            assert_eq!(
+                calc.update_view(),
+                changed,
+                "Should only notify when {} changes the view.",
+                id
+            );
+        }
+
+        calc.calculator.set_display_width(calc.display_width());
+        assert!(
+            !calc.update_view(),
+            "Should not notify when a setting is unchanged."
+        );
+        calc.calculator.set_display_mode(DisplayMode::Fix(2));
+        assert!(calc.update_view());
+        assert_eq!(calc.view.display, "9.00");
+    }
+
+    #[test]
+    fn state() {
+        let mut calc = WasmIosCalculator::new();
+        assert_eq!(calc.state().clear_label(), "AC");
+        assert_eq!(calc.state().angle_mode(), "degrees");
+
+        calc.button_pressed("Rad");
+        calc.button_pressed("1");
+        calc.button_pressed("2");
+        calc.button_pressed("+");
+
+        let state = calc.state();
+        assert_eq!(state.display(), calc.output());
+        assert_eq!(state.expression(), "12 +");
+        assert_eq!(state.active_operator(), Some('+'));
+        assert_eq!(state.angle_mode(), "radians");
+        assert!(!state.show_all_clear());
+        assert!(!state.memory());
+        assert!(!state.error());
+
+        calc.button_pressed("AC");
+        calc.button_pressed("AC");
+
+        calc.button_pressed("2");
+        calc.button_pressed("/");
+        calc.button_pressed("0");
+        calc.button_pressed("=");
+
+        let state = calc.state();
+        assert_eq!(state.clear_label(), "C");
+        assert_eq!(state.expression(), "Error");
+        assert!(state.error());
+        assert!(state.memory());
+        assert_eq!(state.active_operator(), None);
+    }
+
+    #[test]
+    fn aria_label() {
+        let mut calc = WasmIosCalculator::new();
+        for id in ["1", "2", "0", "0", ".", "5", "+"] {
+            calc.button_pressed(id);
+        }
+        assert_eq!(
+            calc.aria_label(),
+            "one thousand two hundred point five plus"
+        );
+        assert_eq!(calc.state().aria_label(), calc.aria_label());
+
+        for id in ["AC", "AC", "1", ".", "2", "EE", "9", "="] {
+            calc.button_pressed(id);
+        }
+        assert_eq!(calc.aria_label(), "one point two times ten to the ninth");
+    }
+
     #[wasm_bindgen_test]
     fn initial_result() {
         assert_eq!(calc!(), "0");
@@ -125,6 +419,33 @@ mod test {
         assert_eq!(calc.active_operator(), Some('+'));
     }
 
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    async fn subscriber_reads_calculator() {
+        let calc = JsValue::from(WasmIosCalculator::new());
+        let outputs = js_sys::Array::new();
+        let press = js_sys::Function::new_with_args(
+            "calc, outputs",
+            "calc.subscribe(() => outputs.push(calc.output)); calc.buttonPressed('7');",
+        );
+        press.call2(&JsValue::NULL, &calc, &outputs).unwrap();
+
+        // Wait for the subscriber's microtask.
+        wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL))
+            .await
+            .unwrap();
+        assert_eq!(
+            outputs.to_vec(),
+            vec![JsValue::from("7")],
+            "Should be able to read the calculator from a subscriber."
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn unknown_button() {
+        assert_eq!(calc!("1", "?"), "1");
+    }
+
     #[wasm_bindgen_test]
     fn sign_function() {
         assert_eq!(calc!("1", "±"), "-1");
@@ -241,7 +562,7 @@ mod test {
     #[wasm_bindgen_test]
     fn big_number_exponential_truncation_a() {
         assert_eq!(
-            calc!("1", "2", "3", "4", "5", "6", "7", "8", "9", "*", "10", "="),
+            calc!("1", "2", "3", "4", "5", "6", "7", "8", "9", "*", "1", "0", "="),
             "1.234567e9"
         );
     }
@@ -249,7 +570,7 @@ mod test {
     #[wasm_bindgen_test]
     fn big_number_exponential_truncation_b() {
         assert_eq!(
-            calc!("1", "2", "3", "4", "5", "6", "7", "8", "9", "*", "100", "="),
+            calc!("1", "2", "3", "4", "5", "6", "7", "8", "9", "*", "1", "0", "0", "="),
             "1.23456e10"
         );
     }
@@ -257,7 +578,7 @@ mod test {
     #[wasm_bindgen_test]
     fn big_neg_number_exponential_truncation() {
         assert_eq!(
-            calc!("-", "1", "2", "3", "4", "5", "6", "7", "8", "9", "*", "100", "="),
+            calc!("-", "1", "2", "3", "4", "5", "6", "7", "8", "9", "*", "1", "0", "0", "="),
             "-1.23456e10"
         );
     }
@@ -283,4 +604,187 @@ mod test {
     fn order_of_ops_calculation() {
         assert_eq!(calc!("1", "+", "2", "*", "3", "="), "7");
     }
+
+    #[wasm_bindgen_test]
+    fn square_root() {
+        assert_eq!(calc!("9", "²√"), "3");
+    }
+
+    #[wasm_bindgen_test]
+    fn square_root_negative_real() {
+        assert_eq!(calc!("4", "±", "²√"), "Error");
+    }
+
+    #[wasm_bindgen_test]
+    fn square_root_negative_complex() {
+        let mut calc = WasmIosCalculator::new();
+        calc.set_complex_mode(true);
+        for id in ["4", "±", "²√"] {
+            calc.button_pressed(id);
+        }
+        assert_eq!(calc.output(), "2i");
+    }
+
+    #[wasm_bindgen_test]
+    fn complex_add() {
+        let mut calc = WasmIosCalculator::new();
+        calc.set_complex_mode(true);
+        for id in ["2", "+", "3", "i", "="] {
+            calc.button_pressed(id);
+        }
+        assert_eq!(calc.output(), "2+3i");
+    }
+
+    #[wasm_bindgen_test]
+    fn complex_polar() {
+        let mut calc = WasmIosCalculator::new();
+        calc.set_complex_mode(true);
+        for id in ["2", "i", "∠"] {
+            calc.button_pressed(id);
+        }
+        assert_eq!(calc.output(), "2∠90°");
+        calc.button_pressed("Rad");
+        assert_eq!(calc.output(), "2∠1.570");
+    }
+
+    #[wasm_bindgen_test]
+    fn display_mode_fix() {
+        let mut calc = WasmIosCalculator::new();
+        calc.set_display_mode("fix", 2);
+        for id in ["1", "0", "/", "3", "="] {
+            calc.button_pressed(id);
+        }
+        assert_eq!(calc.output(), "3.33");
+    }
+
+    #[wasm_bindgen_test]
+    fn display_mode_eng() {
+        let mut calc = WasmIosCalculator::new();
+        for id in ["1", "2", "3", "4", "5", "="] {
+            calc.button_pressed(id);
+        }
+        calc.set_display_mode("eng", 1);
+        assert_eq!(calc.output(), "12.3e3");
+        calc.set_display_mode("normal", 0);
+        assert_eq!(calc.output(), "12,345");
+    }
+
+    #[wasm_bindgen_test]
+    fn display_width_landscape() {
+        let mut calc = WasmIosCalculator::new();
+        calc.set_display_width(15);
+        for _ in 0..10 {
+            calc.button_pressed("9");
+        }
+        assert_eq!(calc.output(), "9,999,999,999");
+        calc.set_display_width(9);
+        assert_eq!(calc.output(), "9.999999e9");
+    }
+
+    #[wasm_bindgen_test]
+    fn entry_limit() {
+        assert_eq!(
+            calc!("1", ".", "2", "3", "4", "5", "6", "7", "8", "9", "1"),
+            "1.23456789"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn exponent_entry() {
+        assert_eq!(calc!("6", ".", "0", "2", "2", "EE", "2", "3"), "6.022e23");
+    }
+
+    #[wasm_bindgen_test]
+    fn exponent_entry_negative() {
+        assert_eq!(calc!("1", "EE", "3", "±", "*", "2", "="), "0.002");
+    }
+
+    #[wasm_bindgen_test]
+    fn pi() {
+        assert_eq!(calc!("π"), "3.14159265");
+    }
+
+    #[wasm_bindgen_test]
+    fn e_replaces_operand() {
+        assert_eq!(calc!("1", "+", "2", "e", "="), "3.71828182");
+    }
+
+    #[wasm_bindgen_test]
+    fn random_seeded() {
+        let mut calc_a = WasmIosCalculator::new();
+        let mut calc_b = WasmIosCalculator::new();
+        calc_a.seed_random(99);
+        calc_b.seed_random(99);
+        calc_a.button_pressed("Rand");
+        calc_b.button_pressed("Rand");
+        assert_eq!(calc_a.output(), calc_b.output());
+    }
+
+    #[wasm_bindgen_test]
+    fn power() {
+        assert_eq!(calc!("2", "^", "1", "0", "="), "1,024");
+    }
+
+    #[wasm_bindgen_test]
+    fn power_right_associative() {
+        assert_eq!(calc!("2", "^", "3", "^", "2", "="), "512");
+    }
+
+    #[wasm_bindgen_test]
+    fn root() {
+        assert_eq!(calc!("2", "7", "√", "3", "="), "3");
+    }
+
+    #[wasm_bindgen_test]
+    fn logarithm() {
+        assert_eq!(calc!("1", "0", "0", "0", "㏒", "1", "0", "="), "3");
+    }
+
+    #[wasm_bindgen_test]
+    fn order_of_ops_resolves_same_order() {
+        let mut calc = WasmIosCalculator::new();
+        for id in ["1", "+", "2", "*", "3", "*"] {
+            calc.button_pressed(id);
+        }
+        assert_eq!(calc.output(), "6");
+        calc.button_pressed("2");
+        calc.button_pressed("=");
+        assert_eq!(calc.output(), "13");
+    }
+
+    #[wasm_bindgen_test]
+    fn percentage_of_sum() {
+        assert_eq!(calc!("2", "0", "0", "-", "1", "5", "%", "="), "170");
+    }
+
+    #[wasm_bindgen_test]
+    fn mixed_order_chain() {
+        assert_eq!(calc!("2", "*", "3", "^", "2", "+", "1", "="), "19");
+    }
+
+    #[wasm_bindgen_test]
+    fn record_macro() {
+        let mut calc = WasmIosCalculator::new();
+        calc.record_macro();
+        assert!(calc.recording());
+        for id in ["*", "1", ".", "2", "+", "1", "5", "="] {
+            calc.button_pressed(id);
+        }
+        assert!(calc.stop_recording("markup"));
+        assert!(!calc.recording());
+        assert_eq!(calc.macros(), vec!["markup"]);
+
+        for id in ["c", "c", "2", "0", "0"] {
+            calc.button_pressed(id);
+        }
+        assert!(calc.play_macro("markup"));
+        assert_eq!(calc.output(), "255");
+    }
+
+    #[wasm_bindgen_test]
+    fn play_unknown_macro() {
+        let mut calc = WasmIosCalculator::new();
+        assert!(!calc.play_macro("missing"));
+        assert!(!calc.stop_recording("missing"));
+    }
 }
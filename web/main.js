@@ -131,17 +131,28 @@ function createKeypadButtons(kepadEl, buttonClickFn) {
 
 /**
  * Outputs to the calculator's display.
- * @param {WasmIosCalculator} calculator 
+ * @param {CalculatorState} state 
  */
-function updateDisplay(calculator) {
+function updateDisplay(state) {
   const displayEl = document.getElementById('display')
+  const spokenEl = document.getElementById('display_spoken')
+
+  if (!displayEl || !spokenEl) {
+    return;
+  }
+
+  // Announce the display as words, live regions only read their text.
+  if (spokenEl.textContent != state.ariaLabel) {
+    spokenEl.textContent = state.ariaLabel;
+  }
+  displayEl.setAttribute('aria-label', state.display);
 
   // Don't update if output is unchanged.
-  if (!displayEl || displayEl.innerText == calculator.output) {
+  if (displayEl.innerText == state.display) {
     return;
   }
 
-  displayEl.innerText = calculator.output;
+  displayEl.innerText = state.display;
 
   // Fit the output font size to the display.
   fitty(displayEl, {
@@ -155,22 +166,22 @@ let activeOperatorBtnEl = undefined;
 
 /**
  * Update calculator view.
- * @param {WasmIosCalculator} calculator 
+ * @param {CalculatorState} state 
  */
-function update(calculator) {
-  updateDisplay(calculator);
+function update(state) {
+  updateDisplay(state);
 
   /* Update clear button. */
   const clearBtnEl = document.getElementById('c');
   if (clearBtnEl) {
-    clearBtnEl.firstElementChild.innerHTML = calculator.showAllClear ? 'AC' : 'C';
+    clearBtnEl.firstElementChild.innerHTML = state.clearLabel;
   }
 
   /* Reset active operator element. */
   activeOperatorBtnEl?.classList.remove('active');
 
-  if (calculator.activeOperator) {
-    activeOperatorBtnEl = document.getElementById(calculator.activeOperator);
+  if (state.activeOperator) {
+    activeOperatorBtnEl = document.getElementById(state.activeOperator);
 
     /* Style active operator. */
     activeOperatorBtnEl?.classList.add('active');
@@ -179,7 +190,6 @@ function update(calculator) {
 
 function handleButtonPress(calculator, key) {
   calculator.buttonPressed(key.toString());
-  update(calculator);
 }
 
 /**
@@ -223,8 +233,11 @@ function create() {
     handleKeyPress(calculator, event.key)
   });
 
+  /* Update the view whenever the calculator's state changes. */
+  calculator.subscribe(update);
+
   /* Initialise calculator */
-  update(calculator);
+  update(calculator.state());
 }
 
 /* Run */